@@ -0,0 +1,276 @@
+use crate::md2pdf::{build_toc_html, html_escape, render_fenced_code_block, unique_slug, TocEntry};
+use std::collections::HashMap;
+
+/// Parse an AsciiDoc (`.adoc`) document into the same `.markdown-body` HTML
+/// fragment, heading list, and document title that [`crate::md2pdf`]'s
+/// Markdown front end produces, so both dialects share one stylesheet and
+/// output pipeline (TOC generation, PDF/HTML/EPUB export).
+///
+/// This covers the common subset used in practice: document/section titles,
+/// paragraphs, single-level bullet/numbered lists, fenced code blocks (with
+/// an optional `[source,lang]` attribute line), `NOTE`/`TIP`/`IMPORTANT`/
+/// `WARNING`/`CAUTION` admonitions, `|===`-delimited tables, and inline
+/// `footnote:[...]` references. It is not a full AsciiDoc implementation
+/// (no nested lists, cross-references, or block attributes beyond `source`).
+pub fn asciidoc_to_html(
+    input: &str,
+    theme: &str,
+) -> (String, Option<String>, Vec<TocEntry>) {
+    let mut html = String::new();
+    let mut toc_entries = Vec::new();
+    let mut slug_counts: HashMap<String, u32> = HashMap::new();
+    let mut document_title: Option<String> = None;
+    let mut footnotes: Vec<String> = Vec::new();
+
+    let mut lines = input.lines().peekable();
+    while let Some(line) = lines.next() {
+        let trimmed_line = line.trim_start();
+        if let Some(level) = heading_level(line) {
+            let text = trimmed_line[level..].trim();
+            let slug = unique_slug(text, &mut slug_counts);
+            let label_html = html_escape(text);
+            if level == 1 && document_title.is_none() {
+                document_title = Some(text.to_string());
+            }
+            toc_entries.push(TocEntry {
+                level: level as u8,
+                slug: slug.clone(),
+                label_html: label_html.clone(),
+            });
+            html.push_str(&format!(
+                "<h{level} id=\"{slug}\">{label_html}</h{level}>\n"
+            ));
+            continue;
+        }
+
+        if line.trim() == "----" {
+            let mut code = String::new();
+            for code_line in lines.by_ref() {
+                if code_line.trim() == "----" {
+                    break;
+                }
+                code.push_str(code_line);
+                code.push('\n');
+            }
+            html.push_str(&render_fenced_code_block("", code.trim_end_matches('\n'), theme));
+            continue;
+        }
+
+        if let Some(lang) = line.trim().strip_prefix("[source,").and_then(|s| s.strip_suffix(']')) {
+            // Consume the `----` delimiter expected on the next line.
+            if lines.peek().map(|l| l.trim() == "----").unwrap_or(false) {
+                lines.next();
+                let mut code = String::new();
+                for code_line in lines.by_ref() {
+                    if code_line.trim() == "----" {
+                        break;
+                    }
+                    code.push_str(code_line);
+                    code.push('\n');
+                }
+                html.push_str(&render_fenced_code_block(lang, code.trim_end_matches('\n'), theme));
+                continue;
+            }
+        }
+
+        if line.trim() == "|===" {
+            let mut rows = Vec::new();
+            for table_line in lines.by_ref() {
+                if table_line.trim() == "|===" {
+                    break;
+                }
+                if table_line.trim().is_empty() {
+                    continue;
+                }
+                let cells: Vec<&str> = table_line
+                    .split('|')
+                    .map(|c| c.trim())
+                    .filter(|c| !c.is_empty())
+                    .collect();
+                rows.push(cells);
+            }
+            html.push_str(&render_table(&rows));
+            continue;
+        }
+
+        if let Some(admonition) = admonition_kind(line) {
+            let body = line.splitn(2, ':').nth(1).unwrap_or("").trim();
+            html.push_str(&format!(
+                "<blockquote class=\"admonition admonition-{}\"><p><span class=\"admonition-label\">{}:</span> {}</p></blockquote>\n",
+                admonition.to_lowercase(),
+                admonition,
+                inline_markup(body, &mut footnotes)
+            ));
+            continue;
+        }
+
+        if let Some(marker) = list_marker(line) {
+            let ordered = marker == '.';
+            let tag = if ordered { "ol" } else { "ul" };
+            html.push_str(&format!("<{tag}>\n"));
+            html.push_str(&format!(
+                "<li>{}</li>\n",
+                inline_markup(line.trim()[1..].trim(), &mut footnotes)
+            ));
+            while let Some(next) = lines.peek() {
+                if list_marker(next) == Some(marker) {
+                    let next = lines.next().unwrap();
+                    html.push_str(&format!(
+                        "<li>{}</li>\n",
+                        inline_markup(next.trim()[1..].trim(), &mut footnotes)
+                    ));
+                } else {
+                    break;
+                }
+            }
+            html.push_str(&format!("</{tag}>\n"));
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        html.push_str(&format!("<p>{}</p>\n", inline_markup(line.trim(), &mut footnotes)));
+    }
+
+    if !footnotes.is_empty() {
+        html.push_str("<div class=\"footnote-definition\">\n<ol>\n");
+        for (i, text) in footnotes.iter().enumerate() {
+            html.push_str(&format!("<li id=\"fn-{}\">{}</li>\n", i + 1, text));
+        }
+        html.push_str("</ol>\n</div>\n");
+    }
+
+    (html, document_title, toc_entries)
+}
+
+/// Combines the AsciiDoc heading/TOC/title output with the shared TOC
+/// renderer, mirroring `markdown_to_html`'s return shape for
+/// `convert_markdown` to consume uniformly.
+pub fn asciidoc_to_document(
+    input: &str,
+    theme: &str,
+    toc: bool,
+    toc_max_depth: Option<u8>,
+) -> (String, Option<String>, Vec<TocEntry>, Option<String>) {
+    let (html, document_title, toc_entries) = asciidoc_to_html(input, theme);
+    let toc_html = if toc {
+        build_toc_html(&toc_entries, toc_max_depth.unwrap_or(3))
+    } else {
+        None
+    };
+    (html, toc_html, toc_entries, document_title)
+}
+
+/// `= Title` through `======` map to `h1`..`h6`; anything else isn't a
+/// heading.
+fn heading_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '=').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if rest.starts_with(' ') {
+        Some(level)
+    } else {
+        None
+    }
+}
+
+fn admonition_kind(line: &str) -> Option<&'static str> {
+    const KINDS: &[&str] = &["NOTE", "TIP", "IMPORTANT", "WARNING", "CAUTION"];
+    let prefix = line.split(':').next()?.trim();
+    KINDS.iter().find(|&&k| k == prefix).copied()
+}
+
+/// `*` for an unordered item, `.` for an ordered one; anything else isn't a
+/// list line.
+fn list_marker(line: &str) -> Option<char> {
+    let trimmed = line.trim_start();
+    let first = trimmed.chars().next()?;
+    if (first == '*' || first == '.') && trimmed.chars().nth(1) == Some(' ') {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+fn render_table(rows: &[Vec<&str>]) -> String {
+    let mut html = String::from("<table>\n");
+    for (i, row) in rows.iter().enumerate() {
+        let cell_tag = if i == 0 { "th" } else { "td" };
+        html.push_str("<tr>");
+        for cell in row {
+            html.push_str(&format!("<{cell_tag}>{}</{cell_tag}>", html_escape(cell)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// Handle the inline markup AsciiDoc and Markdown share in spirit
+/// (`*bold*`, `_italic_`) plus AsciiDoc's `footnote:[...]` syntax, which
+/// collects into `footnotes` and is replaced with a superscript back-link.
+fn inline_markup(text: &str, footnotes: &mut Vec<String>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("footnote:[") {
+        out.push_str(&wrap_emphasis(&rest[..start]));
+        let after = &rest[start + "footnote:[".len()..];
+        let Some(end) = after.find(']') else {
+            out.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        footnotes.push(html_escape(&after[..end]));
+        let n = footnotes.len();
+        out.push_str(&format!("<sup><a href=\"#fn-{n}\">{n}</a></sup>"));
+        rest = &after[end + 1..];
+    }
+    out.push_str(&wrap_emphasis(rest));
+    out
+}
+
+fn wrap_emphasis(text: &str) -> String {
+    let escaped = html_escape(text);
+    let mut out = String::new();
+    let mut chars = escaped.chars().peekable();
+    let mut in_strong = false;
+    let mut in_em = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                out.push_str(if in_strong { "</strong>" } else { "<strong>" });
+                in_strong = !in_strong;
+            }
+            '_' => {
+                out.push_str(if in_em { "</em>" } else { "<em>" });
+                in_em = !in_em;
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// True for input that looks like AsciiDoc rather than Markdown, used when
+/// the caller didn't pass an explicit `--format` flag: a file extension of
+/// `.adoc`/`.asciidoc`, or (as a fallback for stdin-style input) a leading
+/// `= Title` document header.
+pub fn looks_like_asciidoc(input_path: &str, content: &str) -> bool {
+    let ext = std::path::Path::new(input_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    if matches!(ext.as_deref(), Some("adoc") | Some("asciidoc")) {
+        return true;
+    }
+    content
+        .lines()
+        .next()
+        .map(|l| heading_level(l) == Some(1))
+        .unwrap_or(false)
+}
@@ -0,0 +1,416 @@
+use crate::config;
+use futures::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{Emitter, Window};
+
+/// An external binary this module can fetch, cache, and keep up to date from
+/// its GitHub releases. Add a variant (and a matching `LatestVersionApiAdapter`
+/// impl) here for any other tool that should get the same treatment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Ffmpeg,
+    YtDlp,
+}
+
+impl Tool {
+    fn all() -> &'static [Tool] {
+        &[Tool::Ffmpeg, Tool::YtDlp]
+    }
+
+    /// Key used in the manifest and in `setup-progress` events.
+    fn name(&self) -> &'static str {
+        match self {
+            Tool::Ffmpeg => "ffmpeg",
+            Tool::YtDlp => "yt-dlp",
+        }
+    }
+
+    /// Filename the resolved binary is stored under once extracted.
+    fn binary_filename(&self) -> String {
+        let base = match self {
+            Tool::Ffmpeg => "ffmpeg",
+            Tool::YtDlp => "yt-dlp",
+        };
+        if cfg!(windows) {
+            format!("{base}.exe")
+        } else {
+            base.to_string()
+        }
+    }
+
+    fn adapter(&self) -> Box<dyn LatestVersionApiAdapter> {
+        match self {
+            Tool::Ffmpeg => Box::new(FfmpegAdapter),
+            Tool::YtDlp => Box::new(YtDlpAdapter),
+        }
+    }
+}
+
+/// Knows how to pick the right GitHub release asset for one managed tool on
+/// the current host, so `resolve_one` can treat every tool the same way.
+trait LatestVersionApiAdapter: Send + Sync {
+    /// `(owner, repo)` whose `/releases/latest` is queried.
+    fn repo(&self) -> (&'static str, &'static str);
+
+    /// Whether `asset_name` is the release asset for this host.
+    fn asset_matches(&self, asset_name: &str) -> bool;
+}
+
+struct FfmpegAdapter;
+
+impl LatestVersionApiAdapter for FfmpegAdapter {
+    fn repo(&self) -> (&'static str, &'static str) {
+        ("yt-dlp", "FFmpeg-Builds")
+    }
+
+    fn asset_matches(&self, asset_name: &str) -> bool {
+        // yt-dlp/FFmpeg-Builds doesn't publish macOS assets; there's no
+        // platform string that will ever match on that host, so
+        // `resolve_one` reports a clear "no asset found" there instead of
+        // silently grabbing the wrong binary.
+        if cfg!(target_os = "macos") {
+            return false;
+        }
+        let platform = if cfg!(target_os = "windows") {
+            if cfg!(target_arch = "aarch64") {
+                "winarm64"
+            } else {
+                "win64"
+            }
+        } else if cfg!(target_arch = "aarch64") {
+            "linuxarm64"
+        } else {
+            "linux64"
+        };
+
+        asset_name.starts_with(&format!("ffmpeg-master-latest-{platform}-gpl"))
+            && !asset_name.contains("-shared")
+    }
+}
+
+struct YtDlpAdapter;
+
+impl LatestVersionApiAdapter for YtDlpAdapter {
+    fn repo(&self) -> (&'static str, &'static str) {
+        ("yt-dlp", "yt-dlp")
+    }
+
+    fn asset_matches(&self, asset_name: &str) -> bool {
+        let expected = if cfg!(target_os = "windows") {
+            "yt-dlp.exe"
+        } else if cfg!(target_os = "macos") {
+            "yt-dlp_macos"
+        } else if cfg!(target_arch = "aarch64") {
+            "yt-dlp_linux_aarch64"
+        } else {
+            "yt-dlp_linux"
+        };
+        asset_name == expected
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+async fn fetch_latest_release(client: &Client, owner: &str, repo: &str) -> Result<GithubRelease, String> {
+    let url = format!("https://api.github.com/repos/{owner}/{repo}/releases/latest");
+    client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query {}: {}", url, e))?
+        .json::<GithubRelease>()
+        .await
+        .map_err(|e| format!("Failed to parse release JSON from {}: {}", url, e))
+}
+
+/// What's currently cached on disk for each managed tool, keyed by
+/// [`Tool::name`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    entries: HashMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    version: String,
+    path: PathBuf,
+}
+
+fn bin_dir() -> PathBuf {
+    config::config_dir().join("bin")
+}
+
+fn manifest_path() -> PathBuf {
+    bin_dir().join("manifest.json")
+}
+
+fn load_manifest() -> Manifest {
+    std::fs::read_to_string(manifest_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(manifest: &Manifest) -> Result<(), String> {
+    let path = manifest_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let contents = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize binary manifest: {}", e))?;
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// The cached path for `tool`, if `resolve_binaries` has already fetched one
+/// and it's still present on disk. Consulted by `ffmpeg::get_ffmpeg_path` and
+/// `YtDlpExtractor::binary` before they fall back to their own PATH lookup.
+pub fn resolved_path(tool: Tool) -> Option<PathBuf> {
+    load_manifest()
+        .entries
+        .get(tool.name())
+        .map(|e| e.path.clone())
+        .filter(|p| p.exists())
+}
+
+/// Installed version per tool, for a first-run setup screen to display
+/// without triggering any network activity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinaryStatus {
+    pub ffmpeg: Option<String>,
+    pub yt_dlp: Option<String>,
+}
+
+fn status_from_manifest(manifest: &Manifest) -> BinaryStatus {
+    BinaryStatus {
+        ffmpeg: manifest.entries.get(Tool::Ffmpeg.name()).map(|e| e.version.clone()),
+        yt_dlp: manifest.entries.get(Tool::YtDlp.name()).map(|e| e.version.clone()),
+    }
+}
+
+/// Read-only view of `get_binary_status`'s answer without hitting the
+/// network or re-resolving anything.
+pub fn get_binary_status() -> BinaryStatus {
+    status_from_manifest(&load_manifest())
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SetupProgress {
+    tool: &'static str,
+    phase: &'static str,
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+/// Re-check every managed tool's latest GitHub release against the cached
+/// manifest, downloading and extracting a fresh copy when one is missing or
+/// out of date. Safe to call on every launch: a tool whose cached version
+/// already matches the latest release is left untouched. Emits
+/// `setup-progress` throughout so the frontend can show a first-run setup
+/// screen; a single tool failing to resolve (e.g. no network, or no release
+/// asset for this platform) doesn't stop the others.
+pub async fn resolve_binaries(window: Window) -> Result<BinaryStatus, String> {
+    let client = Client::builder()
+        .user_agent("vget-binary-resolver")
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    let mut manifest = load_manifest();
+
+    for tool in Tool::all() {
+        if let Err(e) = resolve_one(&client, &window, *tool, &mut manifest).await {
+            eprintln!("[binary_resolver] failed to resolve {}: {}", tool.name(), e);
+        }
+    }
+
+    save_manifest(&manifest)?;
+    Ok(status_from_manifest(&manifest))
+}
+
+async fn resolve_one(client: &Client, window: &Window, tool: Tool, manifest: &mut Manifest) -> Result<(), String> {
+    let adapter = tool.adapter();
+    let (owner, repo) = adapter.repo();
+    let release = fetch_latest_release(client, owner, repo).await?;
+
+    if let Some(entry) = manifest.entries.get(tool.name()) {
+        if entry.version == release.tag_name && entry.path.exists() {
+            return Ok(());
+        }
+    }
+
+    let asset = release
+        .assets
+        .iter()
+        .find(|a| adapter.asset_matches(&a.name))
+        .ok_or_else(|| format!("No release asset for {} matches this platform", tool.name()))?;
+
+    let dest_dir = bin_dir();
+    tokio::fs::create_dir_all(&dest_dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", dest_dir.display(), e))?;
+
+    let data = download_with_progress(client, window, tool, &asset.browser_download_url).await?;
+
+    let _ = window.emit(
+        "setup-progress",
+        &SetupProgress {
+            tool: tool.name(),
+            phase: "extract",
+            downloaded: data.len() as u64,
+            total: Some(data.len() as u64),
+        },
+    );
+
+    let binary_path = extract_binary(&data, &asset.name, &dest_dir, tool)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&binary_path)
+            .map_err(|e| format!("Failed to stat {}: {}", binary_path.display(), e))?
+            .permissions();
+        perms.set_mode(perms.mode() | 0o111);
+        std::fs::set_permissions(&binary_path, perms)
+            .map_err(|e| format!("Failed to chmod {}: {}", binary_path.display(), e))?;
+    }
+
+    manifest.entries.insert(
+        tool.name().to_string(),
+        ManifestEntry {
+            version: release.tag_name,
+            path: binary_path,
+        },
+    );
+
+    Ok(())
+}
+
+/// Stream `url` into memory, emitting `setup-progress` at most every 100ms —
+/// the same throttling `SimpleDownloader` uses for `download-progress`.
+async fn download_with_progress(client: &Client, window: &Window, tool: Tool, url: &str) -> Result<Vec<u8>, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error fetching {}: {}", url, response.status()));
+    }
+
+    let total = response.content_length();
+    let mut downloaded = 0u64;
+    let mut buf = Vec::new();
+    let mut stream = response.bytes_stream();
+    let mut last_emit = Instant::now();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Stream error downloading {}: {}", tool.name(), e))?;
+        downloaded += chunk.len() as u64;
+        buf.extend_from_slice(&chunk);
+
+        if last_emit.elapsed().as_millis() >= 100 {
+            let _ = window.emit(
+                "setup-progress",
+                &SetupProgress {
+                    tool: tool.name(),
+                    phase: "download",
+                    downloaded,
+                    total,
+                },
+            );
+            last_emit = Instant::now();
+        }
+    }
+
+    let _ = window.emit(
+        "setup-progress",
+        &SetupProgress {
+            tool: tool.name(),
+            phase: "download",
+            downloaded,
+            total,
+        },
+    );
+
+    Ok(buf)
+}
+
+/// Pull `tool`'s binary out of `data` (named `asset_name` by GitHub) into
+/// `dest_dir`, picking the archive format from the asset's extension. An
+/// asset with no recognized archive extension is assumed to already *be*
+/// the binary (as yt-dlp's release assets are).
+fn extract_binary(data: &[u8], asset_name: &str, dest_dir: &Path, tool: Tool) -> Result<PathBuf, String> {
+    let target = dest_dir.join(tool.binary_filename());
+
+    if asset_name.ends_with(".zip") {
+        extract_from_zip(data, tool, &target)?;
+    } else if asset_name.ends_with(".tar.xz") {
+        extract_from_tar_reader(xz2::read::XzDecoder::new(data), tool, &target)?;
+    } else if asset_name.ends_with(".tar.gz") || asset_name.ends_with(".tgz") {
+        extract_from_tar_reader(flate2::read::GzDecoder::new(data), tool, &target)?;
+    } else {
+        std::fs::write(&target, data).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    }
+
+    Ok(target)
+}
+
+fn extract_from_zip(data: &[u8], tool: Tool, target: &Path) -> Result<(), String> {
+    let mut archive =
+        zip::ZipArchive::new(std::io::Cursor::new(data)).map_err(|e| format!("Failed to open zip: {}", e))?;
+    let binary_name = tool.binary_filename();
+
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let entry_name = entry.name().to_string();
+
+        if entry_name.rsplit('/').next() == Some(binary_name.as_str()) {
+            let mut out =
+                std::fs::File::create(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+            std::io::copy(&mut entry, &mut out).map_err(|e| format!("Failed to extract {}: {}", entry_name, e))?;
+            return Ok(());
+        }
+    }
+
+    Err(format!("{} not found inside zip archive", binary_name))
+}
+
+fn extract_from_tar_reader<R: Read>(reader: R, tool: Tool, target: &Path) -> Result<(), String> {
+    let mut archive = tar::Archive::new(reader);
+    let binary_name = tool.binary_filename();
+
+    for entry in archive.entries().map_err(|e| format!("Failed to read tar: {}", e))? {
+        let mut entry = entry.map_err(|e| format!("Failed to read tar entry: {}", e))?;
+        let path = entry
+            .path()
+            .map_err(|e| format!("Failed to read tar entry path: {}", e))?
+            .to_path_buf();
+
+        if path.file_name().and_then(|n| n.to_str()) == Some(binary_name.as_str()) {
+            let mut out =
+                std::fs::File::create(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+            std::io::copy(&mut entry, &mut out).map_err(|e| format!("Failed to extract tar entry: {}", e))?;
+            return Ok(());
+        }
+    }
+
+    Err(format!("{} not found inside tar archive", binary_name))
+}
@@ -20,18 +20,43 @@ pub struct TwitterConfig {
 pub struct ServerConfig {
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent: u32,
+    /// Maximum attempts (including the first) the retry helper makes for a
+    /// request before giving up.
+    #[serde(default = "default_retry_max_attempts")]
+    pub retry_max_attempts: u32,
+    /// Backoff before the first retry, in milliseconds; doubles after each
+    /// subsequent retry.
+    #[serde(default = "default_retry_initial_backoff_ms")]
+    pub retry_initial_backoff_ms: u64,
 }
 
 fn default_max_concurrent() -> u32 {
     10
 }
 
+fn default_retry_max_attempts() -> u32 {
+    5
+}
+
+fn default_retry_initial_backoff_ms() -> u64 {
+    500
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BilibiliConfig {
     #[serde(default)]
     pub cookie: Option<String>,
 }
 
+/// Controls for the optional Discord Rich Presence integration (`presence`
+/// module, behind the `discord-presence` build feature). Defaults to
+/// disabled since sharing activity with Discord is opt-in.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PresenceConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Kuaidi100Config {
     #[serde(default)]
@@ -46,6 +71,26 @@ pub struct ExpressConfig {
     pub kuaidi100: Option<Kuaidi100Config>,
 }
 
+/// Caps the `ffmpeg_*` commands' preflight step enforces against a probed
+/// input before it will spawn the real job — independent of, and checked
+/// earlier than, `MergeLimits` (which only gates `validate_for_merge`).
+/// `None`/empty means no restriction on that axis.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FfmpegLimits {
+    #[serde(default)]
+    pub max_width: Option<u32>,
+    #[serde(default)]
+    pub max_height: Option<u32>,
+    #[serde(default)]
+    pub max_duration_secs: Option<f64>,
+    #[serde(default)]
+    pub allowed_video_codecs: Option<Vec<String>>,
+    #[serde(default)]
+    pub allowed_audio_codecs: Option<Vec<String>>,
+    #[serde(default)]
+    pub allowed_containers: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default = "default_language")]
@@ -56,6 +101,11 @@ pub struct Config {
     pub format: String,
     #[serde(default = "default_quality")]
     pub quality: String,
+    /// Preferred video codec (`"av1"`, `"hevc"`, `"avc"`), matched against a
+    /// `Format`'s `quality` label during format selection. `None` means no
+    /// preference.
+    #[serde(default)]
+    pub codec: Option<String>,
     #[serde(default, rename = "webdavServers")]
     pub webdav_servers: HashMap<String, WebDAVServer>,
     #[serde(default)]
@@ -66,6 +116,10 @@ pub struct Config {
     pub express: ExpressConfig,
     #[serde(default)]
     pub bilibili: BilibiliConfig,
+    #[serde(default)]
+    pub ffmpeg_limits: FfmpegLimits,
+    #[serde(default)]
+    pub presence: PresenceConfig,
 }
 
 fn default_language() -> String {
@@ -93,18 +147,22 @@ impl Default for Config {
             output_dir: default_output_dir(),
             format: default_format(),
             quality: default_quality(),
+            codec: None,
             webdav_servers: HashMap::new(),
             twitter: TwitterConfig::default(),
             server: ServerConfig::default(),
             express: ExpressConfig::default(),
             bilibili: BilibiliConfig::default(),
+            ffmpeg_limits: FfmpegLimits::default(),
+            presence: PresenceConfig::default(),
         }
     }
 }
 
-fn config_dir() -> PathBuf {
-    // Share config with CLI: ~/.config/vget/
-    // Don't use dirs::config_dir() as it returns ~/Library/Application Support/ on macOS
+/// Share config (and any other app state) with the CLI: `~/.config/vget/`.
+/// Don't use `dirs::config_dir()`, as it returns `~/Library/Application
+/// Support/` on macOS.
+pub fn config_dir() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join(".config")
@@ -0,0 +1,299 @@
+use super::{DownloadProgress, DownloadStatus};
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, KeyIvInit};
+use reqwest::Client;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+use tauri::{Emitter, Window};
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::watch::Receiver;
+use url::Url;
+
+type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+
+/// One variant sub-playlist URI from a master playlist, resolved to an
+/// absolute URL.
+struct Variant {
+    url: Url,
+    /// Vertical resolution advertised in `#EXT-X-STREAM-INF`, used to pick
+    /// the variant closest to the requested quality.
+    height: Option<u32>,
+}
+
+/// Downloads an HLS (`.m3u8`) stream: resolves a master playlist down to a
+/// media playlist if needed, then fetches every segment in order and
+/// concatenates them into `output_path`. Byte size isn't known up front, so
+/// progress is reported in segments rather than bytes.
+pub struct HlsDownloader {
+    client: Client,
+}
+
+impl HlsDownloader {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Fetch and download an HLS stream at `url`, selecting the variant
+    /// closest to `preferred_height` if `url` turns out to be a master
+    /// playlist.
+    ///
+    /// `segments`/`key_uri`/`key_iv` let a caller that already resolved a
+    /// media playlist (see `Format::segments` in the direct-link extractor)
+    /// hand the segment list straight over instead of having it re-fetched
+    /// and re-parsed here; when `key_uri` is set, each segment is decrypted
+    /// with AES-128-CBC before being written out.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn download(
+        &self,
+        job_id: &str,
+        url: &str,
+        output_path: &str,
+        window: &Window,
+        cancel_rx: Receiver<bool>,
+        headers: Option<HashMap<String, String>>,
+        preferred_height: Option<u32>,
+        segments: Option<Vec<String>>,
+        key_uri: Option<String>,
+        key_iv: Option<String>,
+    ) -> Result<(), String> {
+        let segments: Vec<Url> = match segments {
+            Some(segs) if !segs.is_empty() => segs
+                .iter()
+                .map(|s| Url::parse(s).map_err(|e| format!("Invalid segment URL: {}", e)))
+                .collect::<Result<_, _>>()?,
+            _ => {
+                let base = Url::parse(url).map_err(|e| format!("Invalid playlist URL: {}", e))?;
+                let playlist_url = self
+                    .resolve_media_playlist(&base, headers.as_ref(), preferred_height)
+                    .await?;
+
+                let body = self.fetch_text(&playlist_url, headers.as_ref()).await?;
+                body.lines()
+                    .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+                    .map(|line| resolve_uri(&playlist_url, line.trim()))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+
+        if segments.is_empty() {
+            return Err("Playlist contains no segments".to_string());
+        }
+
+        let decryption_key = match &key_uri {
+            Some(uri) => {
+                let key_url = Url::parse(uri).map_err(|e| format!("Invalid key URI: {}", e))?;
+                let bytes = self.fetch_bytes(&key_url, headers.as_ref()).await?;
+                if bytes.len() != 16 {
+                    return Err(format!("Unexpected AES-128 key length: {} bytes", bytes.len()));
+                }
+                let mut key = [0u8; 16];
+                key.copy_from_slice(&bytes);
+                Some(key)
+            }
+            None => None,
+        };
+
+        if let Some(parent) = Path::new(output_path).parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+
+        let part_path = format!("{}.part", output_path);
+        let mut file = File::create(&part_path)
+            .await
+            .map_err(|e| format!("Failed to create file: {}", e))?;
+
+        let total = segments.len() as u64;
+        let mut last_emit = Instant::now();
+
+        for (index, segment_url) in segments.iter().enumerate() {
+            if *cancel_rx.borrow() {
+                drop(file);
+                let _ = tokio::fs::remove_file(&part_path).await;
+                return Err("Download cancelled".to_string());
+            }
+
+            let mut bytes = self.fetch_bytes(segment_url, headers.as_ref()).await?;
+            if let Some(key) = &decryption_key {
+                let iv = segment_iv(key_iv.as_deref(), index as u64);
+                bytes = decrypt_segment(&bytes, key, &iv)?;
+            }
+            file.write_all(&bytes)
+                .await
+                .map_err(|e| format!("Write error: {}", e))?;
+
+            let downloaded = (index + 1) as u64;
+            if last_emit.elapsed().as_millis() >= 100 || downloaded == total {
+                let progress = DownloadProgress {
+                    job_id: job_id.to_string(),
+                    downloaded,
+                    total: Some(total),
+                    speed: 0,
+                    percent: (downloaded as f64 / total as f64) * 100.0,
+                };
+                let _ = window.emit("download-progress", &progress);
+                last_emit = Instant::now();
+            }
+        }
+
+        file.flush().await.map_err(|e| format!("Flush error: {}", e))?;
+        drop(file);
+
+        tokio::fs::rename(&part_path, output_path)
+            .await
+            .map_err(|e| format!("Failed to finalize download: {}", e))?;
+
+        let _ = window.emit(
+            "download-complete",
+            serde_json::json!({
+                "jobId": job_id,
+                "status": DownloadStatus::Completed,
+                "outputPath": output_path,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// If `url` points at a master playlist, parse its variant streams and
+    /// return the one closest to `preferred_height`; otherwise `url` is
+    /// already a media playlist and is returned unchanged.
+    async fn resolve_media_playlist(
+        &self,
+        url: &Url,
+        headers: Option<&HashMap<String, String>>,
+        preferred_height: Option<u32>,
+    ) -> Result<Url, String> {
+        let body = self.fetch_text(url, headers).await?;
+
+        if !body.contains("#EXT-X-STREAM-INF") {
+            return Ok(url.clone());
+        }
+
+        let mut variants = Vec::new();
+        let mut pending_height = None;
+        for line in body.lines() {
+            let line = line.trim();
+            if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+                pending_height = parse_resolution_height(attrs);
+            } else if !line.is_empty() && !line.starts_with('#') {
+                variants.push(Variant {
+                    url: resolve_uri(url, line)?,
+                    height: pending_height.take(),
+                });
+            }
+        }
+
+        if variants.is_empty() {
+            return Err("Master playlist contains no variants".to_string());
+        }
+
+        let chosen = match preferred_height {
+            Some(target) => variants.into_iter().min_by_key(|v| {
+                v.height.map(|h| (h as i64 - target as i64).unsigned_abs()).unwrap_or(u64::MAX)
+            }),
+            None => variants.into_iter().max_by_key(|v| v.height.unwrap_or(0)),
+        };
+
+        Ok(chosen.expect("checked non-empty above").url)
+    }
+
+    async fn fetch_text(&self, url: &Url, headers: Option<&HashMap<String, String>>) -> Result<String, String> {
+        let bytes = self.fetch_bytes(url, headers).await?;
+        String::from_utf8(bytes).map_err(|e| format!("Playlist is not valid UTF-8: {}", e))
+    }
+
+    async fn fetch_bytes(
+        &self,
+        url: &Url,
+        headers: Option<&HashMap<String, String>>,
+    ) -> Result<Vec<u8>, String> {
+        let mut request = self.client.get(url.clone());
+        if let Some(hdrs) = headers {
+            for (key, value) in hdrs {
+                request = request.header(key, value);
+            }
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP error fetching {}: {}", url, response.status()));
+        }
+
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|e| format!("Failed to read response body: {}", e))
+    }
+}
+
+impl Default for HlsDownloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolve a playlist-relative URI (segment or variant sub-playlist) against
+/// the manifest's own URL.
+fn resolve_uri(base: &Url, uri: &str) -> Result<Url, String> {
+    base.join(uri).map_err(|e| format!("Invalid URI in playlist: {}", e))
+}
+
+/// Pull the height out of a `RESOLUTION=WxH` attribute on an
+/// `#EXT-X-STREAM-INF` line.
+fn parse_resolution_height(attrs: &str) -> Option<u32> {
+    attrs
+        .split(',')
+        .find_map(|attr| attr.trim().strip_prefix("RESOLUTION="))
+        .and_then(|res| res.split('x').nth(1))
+        .and_then(|h| h.parse().ok())
+}
+
+/// Decrypt one AES-128-CBC segment with PKCS7 padding, per
+/// `#EXT-X-KEY:METHOD=AES-128`.
+fn decrypt_segment(data: &[u8], key: &[u8; 16], iv: &[u8; 16]) -> Result<Vec<u8>, String> {
+    Aes128CbcDec::new(key.into(), iv.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(data)
+        .map_err(|e| format!("Failed to decrypt HLS segment: {}", e))
+}
+
+/// The IV for segment `sequence`: the `#EXT-X-KEY` attribute's `IV=0x...`
+/// value if present, right-aligned into 16 bytes; otherwise the segment's
+/// sequence number as a big-endian 16-byte integer, per the HLS spec's
+/// default.
+fn segment_iv(key_iv: Option<&str>, sequence: u64) -> [u8; 16] {
+    let mut iv = [0u8; 16];
+    match key_iv.and_then(|v| hex_decode(v.trim_start_matches("0x").trim_start_matches("0X"))) {
+        Some(bytes) => {
+            let len = bytes.len().min(16);
+            iv[16 - len..].copy_from_slice(&bytes[bytes.len() - len..]);
+        }
+        None => iv[8..].copy_from_slice(&sequence.to_be_bytes()),
+    }
+    iv
+}
+
+/// Decode a hex string (no `0x` prefix) into bytes; `None` on any invalid
+/// character or odd length.
+fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
@@ -1,14 +1,49 @@
 use super::{DownloadProgress, DownloadStatus};
 use futures::StreamExt;
 use reqwest::Client;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Instant;
 use tauri::{Emitter, Window};
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use tokio::sync::watch::Receiver;
 
+/// Incremental hasher over one of the digest algorithms callers can request
+/// for post-download integrity checks.
+enum Checksum {
+    Sha256(Sha256),
+    Sha1(Sha1),
+}
+
+impl Checksum {
+    fn new(algorithm: &str) -> Result<Self, String> {
+        match algorithm {
+            "sha256" => Ok(Self::Sha256(Sha256::new())),
+            "sha1" => Ok(Self::Sha1(Sha1::new())),
+            other => Err(format!("Unsupported checksum algorithm: {}", other)),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(h) => h.update(data),
+            Self::Sha1(h) => h.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Sha256(h) => format!("{:x}", h.finalize()),
+            Self::Sha1(h) => format!("{:x}", h.finalize()),
+        }
+    }
+}
+
 pub struct SimpleDownloader {
     client: Client,
 }
@@ -32,6 +67,28 @@ impl SimpleDownloader {
         cancel_rx: Receiver<bool>,
         headers: Option<HashMap<String, String>>,
     ) -> Result<(), String> {
+        self.download_checked(job_id, url, output_path, window, cancel_rx, headers, None)
+            .await
+    }
+
+    /// Like `download`, but when `expected_digest` is `Some((algorithm, hex_digest))`
+    /// the downloaded bytes are hashed as they're written and verified before the
+    /// file is considered complete.
+    pub async fn download_checked(
+        &self,
+        job_id: &str,
+        url: &str,
+        output_path: &str,
+        window: &Window,
+        cancel_rx: Receiver<bool>,
+        headers: Option<HashMap<String, String>>,
+        expected_digest: Option<(&str, &str)>,
+    ) -> Result<(), String> {
+        let mut hasher = match expected_digest {
+            Some((algorithm, _)) => Some(Checksum::new(algorithm)?),
+            None => None,
+        };
+
         // Ensure parent directory exists
         if let Some(parent) = Path::new(output_path).parent() {
             tokio::fs::create_dir_all(parent)
@@ -39,9 +96,21 @@ impl SimpleDownloader {
                 .map_err(|e| format!("Failed to create directory: {}", e))?;
         }
 
+        let part_path = format!("{}.part", output_path);
+
+        // Resume from an existing .part file if one is present
+        let existing_bytes = tokio::fs::metadata(&part_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+
         // Start download with optional headers
         let mut request = self.client.get(url);
 
+        if existing_bytes > 0 {
+            request = request.header("Range", format!("bytes={}-", existing_bytes));
+        }
+
         if let Some(hdrs) = headers {
             for (key, value) in hdrs {
                 request = request.header(&key, &value);
@@ -57,15 +126,47 @@ impl SimpleDownloader {
             return Err(format!("HTTP error: {}", response.status()));
         }
 
-        let total = response.content_length();
-        let mut downloaded: u64 = 0;
+        // The server may ignore our Range header and send the whole file back;
+        // only treat this as a resume if it actually confirmed partial content.
+        let resumed = existing_bytes > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+
+        let total = response
+            .content_length()
+            .map(|len| if resumed { len + existing_bytes } else { len });
+        let mut downloaded: u64 = if resumed { existing_bytes } else { 0 };
         let mut last_emit = Instant::now();
-        let mut last_downloaded: u64 = 0;
+        let mut last_downloaded: u64 = downloaded;
 
-        // Create file
-        let mut file = File::create(output_path)
-            .await
-            .map_err(|e| format!("Failed to create file: {}", e))?;
+        // Open the part file, appending if we're resuming, truncating otherwise
+        let mut file = if resumed {
+            // Catch the hasher up on the bytes we already have on disk.
+            if let Some(hasher) = &mut hasher {
+                let mut existing = File::open(&part_path)
+                    .await
+                    .map_err(|e| format!("Failed to open part file: {}", e))?;
+                let mut buf = [0u8; 64 * 1024];
+                loop {
+                    let n = existing
+                        .read(&mut buf)
+                        .await
+                        .map_err(|e| format!("Failed to read part file: {}", e))?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buf[..n]);
+                }
+            }
+
+            OpenOptions::new()
+                .append(true)
+                .open(&part_path)
+                .await
+                .map_err(|e| format!("Failed to open part file: {}", e))?
+        } else {
+            File::create(&part_path)
+                .await
+                .map_err(|e| format!("Failed to create file: {}", e))?
+        };
 
         // Stream download
         let mut stream = response.bytes_stream();
@@ -74,13 +175,17 @@ impl SimpleDownloader {
             // Check for cancellation
             if *cancel_rx.borrow() {
                 drop(file);
-                let _ = tokio::fs::remove_file(output_path).await;
+                // Leave the .part file in place so a later call can resume it
                 return Err("Download cancelled".to_string());
             }
 
             let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
             downloaded += chunk.len() as u64;
 
+            if let Some(hasher) = &mut hasher {
+                hasher.update(&chunk);
+            }
+
             file.write_all(&chunk)
                 .await
                 .map_err(|e| format!("Write error: {}", e))?;
@@ -114,6 +219,27 @@ impl SimpleDownloader {
         file.flush()
             .await
             .map_err(|e| format!("Flush error: {}", e))?;
+        drop(file);
+
+        // Verify integrity before the file is considered complete
+        let computed_checksum = if let (Some(hasher), Some((_, expected))) = (hasher, expected_digest) {
+            let actual = hasher.finalize_hex();
+            if !actual.eq_ignore_ascii_case(expected) {
+                let _ = tokio::fs::remove_file(&part_path).await;
+                return Err(format!(
+                    "Checksum mismatch: expected {}, got {}",
+                    expected, actual
+                ));
+            }
+            Some(actual)
+        } else {
+            None
+        };
+
+        // Only now that the stream has completed (and verified) do we promote the part file
+        tokio::fs::rename(&part_path, output_path)
+            .await
+            .map_err(|e| format!("Failed to finalize download: {}", e))?;
 
         // Emit completion
         let progress = DownloadProgress {
@@ -124,6 +250,279 @@ impl SimpleDownloader {
             percent: 100.0,
         };
 
+        let _ = window.emit("download-progress", &progress);
+        let _ = window.emit(
+            "download-complete",
+            serde_json::json!({
+                "jobId": job_id,
+                "status": DownloadStatus::Completed,
+                "outputPath": output_path,
+                "checksum": computed_checksum,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Like `download_checked`, but retries transient failures with exponential
+    /// backoff. Each retry resumes from the `.part` file left behind by the
+    /// previous attempt instead of starting over.
+    pub async fn download_with_retry(
+        &self,
+        job_id: &str,
+        url: &str,
+        output_path: &str,
+        window: &Window,
+        cancel_rx: Receiver<bool>,
+        headers: Option<HashMap<String, String>>,
+        expected_digest: Option<(&str, &str)>,
+        max_attempts: u32,
+    ) -> Result<(), String> {
+        let max_attempts = max_attempts.max(1);
+        let mut delay = std::time::Duration::from_secs(1);
+        const MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            if *cancel_rx.borrow() {
+                return Err("Download cancelled".to_string());
+            }
+
+            match self
+                .download_checked(
+                    job_id,
+                    url,
+                    output_path,
+                    window,
+                    cancel_rx.clone(),
+                    headers.clone(),
+                    expected_digest,
+                )
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(e) if e.contains("cancelled") => return Err(e),
+                Err(e) if attempt >= max_attempts || !is_retryable_error(&e) => return Err(e),
+                Err(e) => {
+                    eprintln!(
+                        "[downloader] attempt {}/{} failed ({}), retrying in {:?}",
+                        attempt, max_attempts, e, delay
+                    );
+
+                    // Sleep in short slices so cancellation during the backoff is honored
+                    let wait_until = Instant::now() + jitter(delay);
+                    while Instant::now() < wait_until {
+                        if *cancel_rx.borrow() {
+                            return Err("Download cancelled".to_string());
+                        }
+                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    }
+
+                    delay = (delay * 2).min(MAX_DELAY);
+                }
+            }
+        }
+    }
+
+    /// Download a DASH-style pair of separate video/audio tracks (as
+    /// `extractor::Format` stores them via its `audio_url`) to temp files
+    /// beside `output_path`, threading `headers` into both requests so a CDN
+    /// that rejects anonymous clients (e.g. Bilibili) doesn't reject either
+    /// download, then losslessly remuxes them into `output_path` with
+    /// ffmpeg. If ffmpeg isn't on `PATH`, the two downloaded tracks are left
+    /// in place and a clear error names both paths instead of merging.
+    pub async fn download_and_merge(
+        &self,
+        job_id: &str,
+        video_url: &str,
+        audio_url: &str,
+        output_path: &str,
+        window: &Window,
+        cancel_rx: Receiver<bool>,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<(), String> {
+        let video_tmp = format!("{}.video.tmp", output_path);
+        let audio_tmp = format!("{}.audio.tmp", output_path);
+
+        self.download(job_id, video_url, &video_tmp, window, cancel_rx.clone(), headers.clone())
+            .await?;
+        self.download(job_id, audio_url, &audio_tmp, window, cancel_rx, headers)
+            .await?;
+
+        if !crate::ffmpeg::ffmpeg_available() {
+            return Err(format!(
+                "ffmpeg not found on PATH; video and audio saved separately at {} and {}",
+                video_tmp, audio_tmp
+            ));
+        }
+
+        crate::ffmpeg::merge_video_audio(&video_tmp, &audio_tmp, output_path, true).await?;
+
+        let _ = window.emit(
+            "download-complete",
+            serde_json::json!({
+                "jobId": job_id,
+                "status": DownloadStatus::Completed,
+                "outputPath": output_path,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Download a file using `connections` concurrent range requests, falling back
+    /// to the single-stream `download` when the server doesn't support ranges or
+    /// `connections <= 1`.
+    pub async fn download_segmented(
+        &self,
+        job_id: &str,
+        url: &str,
+        output_path: &str,
+        window: &Window,
+        cancel_rx: Receiver<bool>,
+        headers: Option<HashMap<String, String>>,
+        connections: usize,
+    ) -> Result<(), String> {
+        if connections <= 1 {
+            return self.download(job_id, url, output_path, window, cancel_rx, headers).await;
+        }
+
+        // Probe the server for size + range support with a tiny range request,
+        // since plain HEAD responses are unreliable across CDNs.
+        let mut probe = self.client.get(url).header("Range", "bytes=0-0");
+        if let Some(hdrs) = &headers {
+            for (key, value) in hdrs {
+                probe = probe.header(key, value);
+            }
+        }
+
+        let probe_resp = probe
+            .send()
+            .await
+            .map_err(|e| format!("Failed to probe: {}", e))?;
+
+        let accepts_ranges = probe_resp.status() == reqwest::StatusCode::PARTIAL_CONTENT
+            || probe_resp
+                .headers()
+                .get("accept-ranges")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.eq_ignore_ascii_case("bytes"))
+                .unwrap_or(false);
+
+        let total = probe_resp
+            .headers()
+            .get("content-range")
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_content_range_total)
+            .or_else(|| probe_resp.content_length());
+
+        let total = match total {
+            Some(t) if accepts_ranges && t > 0 => t,
+            _ => {
+                // Server doesn't support ranges (or we couldn't learn the size) -
+                // fall back to the existing single-stream path.
+                return self.download(job_id, url, output_path, window, cancel_rx, headers).await;
+            }
+        };
+
+        if let Some(parent) = Path::new(output_path).parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+
+        // Pre-allocate the output file so every task can write into its own slice.
+        {
+            let file = File::create(output_path)
+                .await
+                .map_err(|e| format!("Failed to create file: {}", e))?;
+            file.set_len(total)
+                .await
+                .map_err(|e| format!("Failed to allocate file: {}", e))?;
+        }
+
+        let ranges = split_ranges(total, connections as u64);
+        let downloaded = Arc::new(AtomicU64::new(0));
+
+        let mut tasks = Vec::new();
+        for (start, end) in ranges {
+            let client = self.client.clone();
+            let url = url.to_string();
+            let output_path = output_path.to_string();
+            let headers = headers.clone();
+            let cancel_rx = cancel_rx.clone();
+            let downloaded = downloaded.clone();
+
+            tasks.push(tokio::spawn(async move {
+                download_range(client, &url, &output_path, start, end, headers, cancel_rx, downloaded).await
+            }));
+        }
+
+        // Aggregate the per-task counters into the existing progress event every 100ms.
+        let progress_handle = {
+            let downloaded = downloaded.clone();
+            let window = window.clone();
+            let job_id = job_id.to_string();
+            tokio::spawn(async move {
+                let mut last_emit = Instant::now();
+                let mut last_downloaded = 0u64;
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    let now_downloaded = downloaded.load(Ordering::Relaxed);
+                    if now_downloaded >= total {
+                        break;
+                    }
+                    let elapsed = last_emit.elapsed().as_secs_f64();
+                    let speed = if elapsed > 0.0 {
+                        ((now_downloaded - last_downloaded) as f64 / elapsed) as u64
+                    } else {
+                        0
+                    };
+                    let percent = (now_downloaded as f64 / total as f64) * 100.0;
+                    let _ = window.emit(
+                        "download-progress",
+                        &DownloadProgress {
+                            job_id: job_id.clone(),
+                            downloaded: now_downloaded,
+                            total: Some(total),
+                            speed,
+                            percent,
+                        },
+                    );
+                    last_emit = Instant::now();
+                    last_downloaded = now_downloaded;
+                }
+            })
+        };
+
+        let mut first_err: Option<String> = None;
+        for task in tasks {
+            match task.await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    first_err.get_or_insert(e);
+                }
+                Err(e) => {
+                    first_err.get_or_insert(format!("Task join error: {}", e));
+                }
+            }
+        }
+        progress_handle.abort();
+
+        if let Some(err) = first_err {
+            let _ = tokio::fs::remove_file(output_path).await;
+            return Err(err);
+        }
+
+        let progress = DownloadProgress {
+            job_id: job_id.to_string(),
+            downloaded: total,
+            total: Some(total),
+            speed: 0,
+            percent: 100.0,
+        };
         let _ = window.emit("download-progress", &progress);
         let _ = window.emit(
             "download-complete",
@@ -143,3 +542,147 @@ impl Default for SimpleDownloader {
         Self::new()
     }
 }
+
+/// Split `total` bytes into up to `connections` contiguous, inclusive byte ranges.
+fn split_ranges(total: u64, connections: u64) -> Vec<(u64, u64)> {
+    let connections = connections.max(1).min(total.max(1));
+    let chunk_size = total.div_ceil(connections);
+
+    let mut ranges = Vec::new();
+    let mut start = 0u64;
+    while start < total {
+        let end = (start + chunk_size - 1).min(total - 1);
+        ranges.push((start, end));
+        start = end + 1;
+    }
+    ranges
+}
+
+/// Parse a `Content-Range: bytes 0-0/12345` header into the total size.
+fn parse_content_range_total(header: &str) -> Option<u64> {
+    header.rsplit('/').next()?.parse().ok()
+}
+
+/// Decide whether a download error is worth retrying. Connection resets,
+/// timeouts, mid-stream errors, and 5xx/429 responses are transient; 4xx
+/// responses like 404/403 are not.
+fn is_retryable_error(error: &str) -> bool {
+    if let Some(status) = error
+        .strip_prefix("HTTP error: ")
+        .and_then(|s| s.split_whitespace().next())
+        .and_then(|s| s.parse::<u16>().ok())
+    {
+        return status == 429 || (500..600).contains(&status);
+    }
+
+    error.contains("Failed to fetch")
+        || error.contains("Stream error")
+        || error.contains("Write error")
+        || error.contains("Flush error")
+}
+
+/// Add up to 20% random jitter to a backoff delay.
+fn jitter(delay: std::time::Duration) -> std::time::Duration {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let jitter_frac = (nanos % 1000) as f64 / 1000.0 * 0.2;
+    delay + delay.mul_f64(jitter_frac)
+}
+
+/// Download a single byte range into its slice of the pre-allocated output file.
+async fn download_range(
+    client: Client,
+    url: &str,
+    output_path: &str,
+    start: u64,
+    end: u64,
+    headers: Option<HashMap<String, String>>,
+    cancel_rx: Receiver<bool>,
+    downloaded: Arc<AtomicU64>,
+) -> Result<(), String> {
+    let mut request = client.get(url).header("Range", format!("bytes={}-{}", start, end));
+    if let Some(hdrs) = headers {
+        for (key, value) in hdrs {
+            request = request.header(&key, &value);
+        }
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch range {}-{}: {}", start, end, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error on range {}-{}: {}", start, end, response.status()));
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(output_path)
+        .await
+        .map_err(|e| format!("Failed to open file for range {}-{}: {}", start, end, e))?;
+    file.seek(std::io::SeekFrom::Start(start))
+        .await
+        .map_err(|e| format!("Failed to seek: {}", e))?;
+
+    let mut stream = response.bytes_stream();
+    while let Some(chunk_result) = stream.next().await {
+        if *cancel_rx.borrow() {
+            return Err("Download cancelled".to_string());
+        }
+
+        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Write error: {}", e))?;
+        downloaded.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+    }
+
+    file.flush().await.map_err(|e| format!("Flush error: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_ranges_handles_zero_total() {
+        assert_eq!(split_ranges(0, 4), Vec::new());
+    }
+
+    #[test]
+    fn split_ranges_splits_evenly() {
+        assert_eq!(split_ranges(100, 4), vec![(0, 24), (25, 49), (50, 74), (75, 99)]);
+    }
+
+    #[test]
+    fn split_ranges_handles_remainder() {
+        let ranges = split_ranges(10, 3);
+        assert_eq!(ranges.last().copied(), Some((8, 9)));
+        assert_eq!(ranges.iter().map(|&(s, e)| e - s + 1).sum::<u64>(), 10);
+    }
+
+    #[test]
+    fn split_ranges_clamps_connections_to_total_bytes() {
+        assert_eq!(split_ranges(2, 10).len(), 2);
+    }
+
+    #[test]
+    fn parse_content_range_total_parses_standard_header() {
+        assert_eq!(parse_content_range_total("bytes 0-0/12345"), Some(12345));
+    }
+
+    #[test]
+    fn parse_content_range_total_returns_none_without_total() {
+        assert_eq!(parse_content_range_total("bytes 0-0/*"), None);
+    }
+
+    #[test]
+    fn parse_content_range_total_returns_none_for_malformed_header() {
+        assert_eq!(parse_content_range_total("not a range header"), None);
+    }
+}
@@ -1,3 +1,4 @@
+use super::retry::{send_with_retry, RetryConfig};
 use super::types::*;
 use regex::Regex;
 use reqwest::header::{HeaderMap, HeaderValue, COOKIE, REFERER, USER_AGENT};
@@ -135,13 +136,14 @@ pub struct BilibiliExtractor {
     client: reqwest::Client,
     cookie: Option<String>,
     wbi_key: Option<String>,
+    retry: RetryConfig,
 }
 
 impl BilibiliExtractor {
     pub fn new() -> Self {
-        let cookie = get_config()
-            .ok()
-            .and_then(|c| c.bilibili.cookie);
+        let config = get_config().ok();
+        let cookie = config.as_ref().and_then(|c| c.bilibili.cookie.clone());
+        let retry = config.as_ref().map(|c| RetryConfig::from(&c.server)).unwrap_or_default();
 
         Self {
             client: reqwest::Client::builder()
@@ -151,6 +153,7 @@ impl BilibiliExtractor {
                 .unwrap(),
             cookie,
             wbi_key: None,
+            retry,
         }
     }
 
@@ -160,49 +163,104 @@ impl BilibiliExtractor {
         VIDEO_REGEX.is_match(url_str) || SHORT_REGEX.is_match(url_str)
     }
 
-    /// Extract media info from Bilibili URL
+    /// Extract media info from Bilibili URL, defaulting to the first page of
+    /// a multi-part (分P) upload.
     pub async fn extract(url_str: &str) -> Result<MediaInfo, ExtractError> {
         let mut extractor = Self::new();
         extractor.do_extract(url_str).await
     }
 
+    /// Extract one `MediaInfo` per page of a multi-part (分P) upload, titled
+    /// `"<video title> - P<n>: <part>"`. Single-part uploads yield a
+    /// one-element Vec.
+    pub async fn extract_playlist(url_str: &str) -> Result<Vec<MediaInfo>, ExtractError> {
+        let mut extractor = Self::new();
+        extractor.do_extract_playlist(url_str).await
+    }
+
     async fn do_extract(&mut self, url_str: &str) -> Result<MediaInfo, ExtractError> {
-        // Resolve video ID
+        let (aid, bvid, video_info) = self.resolve_and_fetch_info(url_str).await?;
+
+        let page = video_info
+            .pages
+            .first()
+            .ok_or_else(|| ExtractError::Parse("no video pages found".into()))?;
+
+        self.build_page_media_info(aid, &bvid, &video_info, page, 1).await
+    }
+
+    async fn do_extract_playlist(&mut self, url_str: &str) -> Result<Vec<MediaInfo>, ExtractError> {
+        let (aid, bvid, video_info) = self.resolve_and_fetch_info(url_str).await?;
+
+        if video_info.pages.is_empty() {
+            return Err(ExtractError::Parse("no video pages found".into()));
+        }
+
+        let mut entries = Vec::with_capacity(video_info.pages.len());
+        for (index, page) in video_info.pages.iter().enumerate() {
+            entries.push(
+                self.build_page_media_info(aid, &bvid, &video_info, page, index + 1)
+                    .await?,
+            );
+        }
+
+        Ok(entries)
+    }
+
+    /// Resolve the video ID, fetch WBI keys (non-fatal if it fails), and
+    /// fetch the full video info (including every page) shared by both the
+    /// single-item and playlist extraction paths.
+    async fn resolve_and_fetch_info(&mut self, url_str: &str) -> Result<(i64, String, VideoInfo), ExtractError> {
         let (aid, bvid) = self.resolve_video_id(url_str).await?;
 
-        // Fetch WBI keys (non-fatal if fails)
         if let Err(e) = self.fetch_wbi_keys().await {
             eprintln!("Warning: failed to get WBI keys: {}", e);
         }
 
-        // Fetch video info
         let video_info = self.fetch_video_info(aid).await?;
 
-        // Get first page CID
-        let cid = video_info
-            .pages
-            .first()
-            .map(|p| p.cid)
-            .ok_or_else(|| ExtractError::Parse("no video pages found".into()))?;
-
-        // Fetch play URL to get stream info
-        let streams = self.fetch_play_url(aid, cid).await?;
+        Ok((aid, bvid, video_info))
+    }
 
-        // Build formats from streams
+    /// Fetch stream info for a single page and assemble its `MediaInfo`,
+    /// titling part `page_number` (1-based) as `"<title> - P<n>: <part>"`
+    /// once there's more than one page.
+    async fn build_page_media_info(
+        &self,
+        aid: i64,
+        bvid: &str,
+        video_info: &VideoInfo,
+        page: &Page,
+        page_number: usize,
+    ) -> Result<MediaInfo, ExtractError> {
+        let streams = self.fetch_play_url(aid, page.cid).await?;
         let formats = self.build_formats(&streams);
 
         if formats.is_empty() {
             return Err(ExtractError::NotAvailable);
         }
 
+        let title = if video_info.pages.len() > 1 {
+            format!("{} - P{}: {}", video_info.title, page_number, page.part)
+        } else {
+            video_info.title.clone()
+        };
+
+        let id = if video_info.pages.len() > 1 {
+            format!("{}_p{}", bvid, page_number)
+        } else {
+            bvid.to_string()
+        };
+
         Ok(MediaInfo {
-            id: bvid,
-            title: video_info.title,
-            uploader: Some(video_info.owner.name),
-            thumbnail: Some(video_info.pic),
-            duration: Some(video_info.duration as u64),
+            id,
+            title,
+            uploader: Some(video_info.owner.name.clone()),
+            thumbnail: Some(video_info.pic.clone()),
+            duration: Some(page.duration.max(0) as u64),
             media_type: MediaType::Video,
             formats,
+            media_items: Vec::new(),
         })
     }
 
@@ -240,12 +298,11 @@ impl BilibiliExtractor {
     }
 
     async fn resolve_short_url(&self, short_url: &str) -> Result<String, ExtractError> {
-        let resp = self
-            .client
-            .head(short_url)
-            .header(USER_AGENT, user_agent())
-            .send()
-            .await?;
+        let resp = send_with_retry(
+            || self.client.head(short_url).header(USER_AGENT, user_agent()),
+            &self.retry,
+        )
+        .await?;
 
         if resp.status().is_redirection() {
             if let Some(location) = resp.headers().get("location") {
@@ -259,12 +316,11 @@ impl BilibiliExtractor {
     async fn fetch_wbi_keys(&mut self) -> Result<(), ExtractError> {
         let api = "https://api.bilibili.com/x/web-interface/nav";
 
-        let resp = self
-            .client
-            .get(api)
-            .headers(self.build_headers())
-            .send()
-            .await?;
+        let resp = send_with_retry(
+            || self.client.get(api).headers(self.build_headers()),
+            &self.retry,
+        )
+        .await?;
 
         let data: NavResponse = resp.json().await?;
 
@@ -284,12 +340,11 @@ impl BilibiliExtractor {
             aid
         );
 
-        let resp = self
-            .client
-            .get(&api)
-            .headers(self.build_headers())
-            .send()
-            .await?;
+        let resp = send_with_retry(
+            || self.client.get(&api).headers(self.build_headers()),
+            &self.retry,
+        )
+        .await?;
 
         let data: VideoInfoResponse = resp.json().await?;
 
@@ -320,12 +375,11 @@ impl BilibiliExtractor {
             query
         );
 
-        let resp = self
-            .client
-            .get(&api)
-            .headers(self.build_headers())
-            .send()
-            .await?;
+        let resp = send_with_retry(
+            || self.client.get(&api).headers(self.build_headers()),
+            &self.retry,
+        )
+        .await?;
 
         let data: PlayUrlResponse = resp.json().await?;
 
@@ -409,7 +463,11 @@ impl BilibiliExtractor {
                 height: Some(video.height as u32),
                 filesize: None,
                 audio_url: best_audio_url.clone(),
+                protocol: Some("dash".to_string()),
                 headers: headers.clone(),
+                segments: Vec::new(),
+                key_uri: None,
+                key_iv: None,
             });
         }
 
@@ -532,6 +590,9 @@ struct Owner {
 #[derive(Debug, Deserialize)]
 struct Page {
     cid: i64,
+    part: String,
+    #[serde(default)]
+    duration: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,10 +1,12 @@
+use super::retry::{send_with_retry, RetryConfig};
 use super::types::*;
+use crate::config::get_config;
 use std::collections::HashMap;
 use url::Url;
 
-const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "webm", "avi", "mov", "flv", "m3u8", "ts"];
-const AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "aac", "flac", "wav", "ogg", "opus"];
-const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "bmp", "svg"];
+pub(crate) const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "webm", "avi", "mov", "flv", "m3u8", "ts", "mpd"];
+pub(crate) const AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "aac", "flac", "wav", "ogg", "opus"];
+pub(crate) const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "bmp", "svg"];
 
 pub struct DirectExtractor;
 
@@ -44,11 +46,57 @@ impl DirectExtractor {
             MediaType::Image
         };
 
-        // Try HEAD request to get file size
         let client = reqwest::Client::new();
-        let filesize = client
-            .head(url)
-            .send()
+
+        // An `.m3u8` URL may be a master playlist (multiple selectable
+        // qualities) or a media playlist (a single quality's segment list);
+        // parse it into one or more `Format`s before falling back to
+        // treating it as an opaque direct link.
+        if ext == "m3u8" {
+            if let Some(formats) = Self::extract_hls_formats(&client, &parsed).await {
+                return Ok(MediaInfo {
+                    id: filename.clone(),
+                    title: filename,
+                    uploader: parsed.host_str().map(|s| s.to_string()),
+                    thumbnail: None,
+                    duration: None,
+                    media_type,
+                    formats,
+                    media_items: Vec::new(),
+                });
+            }
+        }
+
+        // A `.mpd` URL is a MPEG-DASH manifest: one `Format` per video
+        // Representation, each paired with the highest-bandwidth audio
+        // Representation's URL so the existing video+audio download path
+        // works unchanged. A manifest that parses but has nothing playable
+        // (e.g. every Representation is `SegmentTemplate`-only) is reported
+        // as an error rather than falling through to the generic direct-link
+        // handling below, which would otherwise silently "download" the raw
+        // manifest XML as if it were a media file.
+        if ext == "mpd" {
+            match Self::extract_dash_formats(&client, &parsed).await {
+                Some(Ok(formats)) => {
+                    return Ok(MediaInfo {
+                        id: filename.clone(),
+                        title: filename,
+                        uploader: parsed.host_str().map(|s| s.to_string()),
+                        thumbnail: None,
+                        duration: None,
+                        media_type,
+                        formats,
+                        media_items: Vec::new(),
+                    });
+                }
+                Some(Err(reason)) => return Err(ExtractError::Parse(reason)),
+                None => {}
+            }
+        }
+
+        // Try HEAD request to get file size
+        let retry = get_config().ok().map(|c| RetryConfig::from(&c.server)).unwrap_or_default();
+        let filesize = send_with_retry(|| client.head(url), &retry)
             .await
             .ok()
             .and_then(|resp| {
@@ -68,6 +116,11 @@ impl DirectExtractor {
             formats: vec![Format {
                 id: "direct".to_string(),
                 url: url.to_string(),
+                protocol: Some(match ext.as_str() {
+                    "m3u8" => "m3u8".to_string(),
+                    "mpd" => "dash".to_string(),
+                    _ => "https".to_string(),
+                }),
                 ext,
                 quality: None,
                 width: None,
@@ -75,7 +128,319 @@ impl DirectExtractor {
                 filesize,
                 audio_url: None,
                 headers: HashMap::new(),
+                segments: Vec::new(),
+                key_uri: None,
+                key_iv: None,
             }],
+            media_items: Vec::new(),
         })
     }
+
+    /// Fetch `playlist_url` and parse it as either an HLS master playlist
+    /// (`#EXT-X-STREAM-INF` variants, one `Format` per quality) or a media
+    /// playlist (`#EXTINF` segments, one `Format` carrying the ordered
+    /// segment list and any `#EXT-X-KEY` decryption info). Returns `None` on
+    /// any fetch/parse failure, or if the body has neither marker, so the
+    /// caller can fall back to a plain direct `Format`.
+    async fn extract_hls_formats(client: &reqwest::Client, playlist_url: &Url) -> Option<Vec<Format>> {
+        let body = client.get(playlist_url.clone()).send().await.ok()?.text().await.ok()?;
+
+        if body.contains("#EXT-X-STREAM-INF") {
+            let mut formats = Vec::new();
+            let mut pending = StreamInf::default();
+            for line in body.lines() {
+                let line = line.trim();
+                if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+                    pending = parse_stream_inf(attrs);
+                } else if !line.is_empty() && !line.starts_with('#') {
+                    let variant_url = resolve_uri(playlist_url, line)?;
+                    formats.push(Format {
+                        id: format!("hls_{}", formats.len()),
+                        url: variant_url.to_string(),
+                        ext: "m3u8".to_string(),
+                        quality: pending.bandwidth.map(|b| format!("{}kbps", b / 1000)),
+                        width: pending.width,
+                        height: pending.height,
+                        filesize: None,
+                        audio_url: None,
+                        protocol: Some("m3u8".to_string()),
+                        headers: HashMap::new(),
+                        segments: Vec::new(),
+                        key_uri: None,
+                        key_iv: None,
+                    });
+                    pending = StreamInf::default();
+                }
+            }
+            return if formats.is_empty() { None } else { Some(formats) };
+        }
+
+        if body.contains("#EXTINF") {
+            let mut segments = Vec::new();
+            let mut key_uri = None;
+            let mut key_iv = None;
+            for line in body.lines() {
+                let line = line.trim();
+                if let Some(attrs) = line.strip_prefix("#EXT-X-KEY:") {
+                    if attrs.contains("METHOD=AES-128") {
+                        key_uri = parse_quoted_attr(attrs, "URI")
+                            .and_then(|u| resolve_uri(playlist_url, &u))
+                            .map(|u| u.to_string());
+                        key_iv = parse_quoted_attr(attrs, "IV");
+                    }
+                } else if !line.is_empty() && !line.starts_with('#') {
+                    segments.push(resolve_uri(playlist_url, line)?.to_string());
+                }
+            }
+            if segments.is_empty() {
+                return None;
+            }
+            return Some(vec![Format {
+                id: "hls_media".to_string(),
+                url: playlist_url.to_string(),
+                ext: "m3u8".to_string(),
+                quality: None,
+                width: None,
+                height: None,
+                filesize: None,
+                audio_url: None,
+                protocol: Some("m3u8".to_string()),
+                headers: HashMap::new(),
+                segments,
+                key_uri,
+                key_iv,
+            }]);
+        }
+
+        None
+    }
+
+    /// Fetch `manifest_url` and parse it as an MPEG-DASH manifest
+    /// (`MPD > Period > AdaptationSet > Representation`), splitting audio
+    /// from video adaptation sets by `mimeType`/`contentType` and emitting
+    /// one video `Format` per video Representation paired with the
+    /// highest-bandwidth audio Representation's URL. Each Representation's
+    /// location comes from its own `BaseURL`; a `SegmentTemplate`-only
+    /// Representation (no `BaseURL`) has no single playable URL — walking a
+    /// full `SegmentTimeline`/`SegmentList` to stitch its segments together
+    /// is out of scope here.
+    ///
+    /// Returns `None` if the body isn't fetchable or doesn't look like a DASH
+    /// manifest at all, so the caller can fall back to a plain direct
+    /// `Format`. Returns `Some(Err(reason))` if the manifest parsed but every
+    /// video Representation turned out to be `SegmentTemplate`-only, so the
+    /// caller can surface a clear error instead of silently downloading the
+    /// manifest XML as if it were a playable file.
+    async fn extract_dash_formats(client: &reqwest::Client, manifest_url: &Url) -> Option<Result<Vec<Format>, String>> {
+        let body = client.get(manifest_url.clone()).send().await.ok()?.text().await.ok()?;
+        if !body.contains("<MPD") {
+            return None;
+        }
+
+        let mut video_reps = Vec::new();
+        let mut audio_reps = Vec::new();
+        let mut current_kind: Option<&str> = None;
+        let mut current_rep: Option<MpdRepresentation> = None;
+        let mut segment_template: Option<(String, u64)> = None;
+
+        let mut pos = 0;
+        while let Some(lt) = body[pos..].find('<') {
+            let tag_start = pos + lt;
+            let Some(gt) = body[tag_start..].find('>') else { break };
+            let tag_end = tag_start + gt;
+            let inner = &body[tag_start + 1..tag_end];
+            let after = &body[tag_end + 1..];
+            let text = match after.find('<') {
+                Some(i) => after[..i].trim(),
+                None => after.trim(),
+            };
+            pos = tag_end + 1;
+
+            if inner.starts_with('?') || inner.starts_with('!') {
+                continue;
+            }
+            let closing = inner.starts_with('/');
+            let attrs = inner.trim_start_matches('/').trim_end_matches('/').trim();
+            let name = attrs.split(char::is_whitespace).next().unwrap_or("");
+
+            match name {
+                "AdaptationSet" if closing => current_kind = None,
+                "AdaptationSet" => current_kind = adaptation_set_kind(attrs),
+                "SegmentTemplate" => {
+                    if let Some(media) = parse_quoted_attr(attrs, "media") {
+                        let start = parse_quoted_attr(attrs, "startNumber")
+                            .and_then(|s| s.parse().ok())
+                            .unwrap_or(1);
+                        segment_template = Some((media, start));
+                    }
+                }
+                "Representation" if closing => {
+                    if let Some(rep) = current_rep.take() {
+                        finalize_representation(rep, current_kind, manifest_url, &segment_template, &mut video_reps, &mut audio_reps);
+                    }
+                }
+                "Representation" => {
+                    let rep = MpdRepresentation {
+                        id: parse_quoted_attr(attrs, "id"),
+                        bandwidth: parse_quoted_attr(attrs, "bandwidth").and_then(|b| b.parse().ok()),
+                        width: parse_quoted_attr(attrs, "width").and_then(|w| w.parse().ok()),
+                        height: parse_quoted_attr(attrs, "height").and_then(|h| h.parse().ok()),
+                        codecs: parse_quoted_attr(attrs, "codecs"),
+                        base_url: None,
+                    };
+                    if inner.ends_with('/') {
+                        finalize_representation(rep, current_kind, manifest_url, &segment_template, &mut video_reps, &mut audio_reps);
+                    } else {
+                        current_rep = Some(rep);
+                    }
+                }
+                "BaseURL" if !closing && !text.is_empty() => {
+                    if let Some(rep) = current_rep.as_mut() {
+                        rep.base_url = resolve_uri(manifest_url, text).map(|u| u.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if video_reps.is_empty() {
+            return Some(Err("DASH manifest has no video Representation".to_string()));
+        }
+
+        let best_audio_url = audio_reps
+            .iter()
+            .max_by_key(|a| a.bandwidth.unwrap_or(0))
+            .and_then(|a| a.base_url.clone());
+
+        let mut formats = Vec::new();
+        let mut segment_template_only = 0;
+        for rep in &video_reps {
+            let Some(url) = &rep.base_url else {
+                segment_template_only += 1;
+                continue;
+            };
+            let quality = match (rep.height, &rep.codecs) {
+                (Some(h), Some(c)) => Some(format!("{}p [{}]", h, c)),
+                (Some(h), None) => Some(format!("{}p", h)),
+                (None, Some(c)) => Some(c.clone()),
+                (None, None) => rep.bandwidth.map(|b| format!("{}kbps", b / 1000)),
+            };
+            formats.push(Format {
+                id: rep.id.clone().unwrap_or_else(|| format!("dash_{}", formats.len())),
+                url: url.clone(),
+                ext: "mp4".to_string(),
+                quality,
+                width: rep.width,
+                height: rep.height,
+                filesize: None,
+                audio_url: best_audio_url.clone(),
+                protocol: Some("dash".to_string()),
+                headers: HashMap::new(),
+                segments: Vec::new(),
+                key_uri: None,
+                key_iv: None,
+            });
+        }
+
+        if !formats.is_empty() {
+            return Some(Ok(formats));
+        }
+
+        if segment_template_only > 0 {
+            return Some(Err(
+                "Unsupported: SegmentTemplate-based DASH manifest (no Representation has a BaseURL) — \
+                 stitching SegmentTimeline/SegmentList segments into a playable file isn't supported yet"
+                    .to_string(),
+            ));
+        }
+
+        None
+    }
+}
+
+/// One `Representation` parsed out of a DASH MPD manifest.
+#[derive(Default)]
+struct MpdRepresentation {
+    id: Option<String>,
+    bandwidth: Option<u64>,
+    width: Option<u32>,
+    height: Option<u32>,
+    codecs: Option<String>,
+    base_url: Option<String>,
+}
+
+/// File a finished `Representation` under video or audio by the adaptation
+/// set it was found in, regardless of whether it has a `BaseURL`.
+/// `segment_template` is intentionally unused here: a Representation with no
+/// `BaseURL` only has a `SegmentTemplate`, which addresses many segments
+/// rather than one playable file, so `base_url` stays `None` — the caller
+/// (`extract_dash_formats`) filters those out when building `Format`s and
+/// reports an error if every Representation turned out that way, instead of
+/// standing in a single unplayable segment URL.
+fn finalize_representation(
+    rep: MpdRepresentation,
+    kind: Option<&str>,
+    _manifest_url: &Url,
+    _segment_template: &Option<(String, u64)>,
+    video_reps: &mut Vec<MpdRepresentation>,
+    audio_reps: &mut Vec<MpdRepresentation>,
+) {
+    match kind {
+        Some("video") => video_reps.push(rep),
+        Some("audio") => audio_reps.push(rep),
+        _ => {}
+    }
+}
+
+/// Classify an `AdaptationSet` tag's attributes as `"video"`/`"audio"` by
+/// `mimeType` (preferred) or `contentType`; `None` for anything else (e.g.
+/// subtitle tracks), which is then skipped.
+fn adaptation_set_kind(attrs: &str) -> Option<&'static str> {
+    let mime = parse_quoted_attr(attrs, "mimeType");
+    let content_type = parse_quoted_attr(attrs, "contentType");
+    if mime.as_deref().is_some_and(|m| m.starts_with("video")) || content_type.as_deref() == Some("video") {
+        Some("video")
+    } else if mime.as_deref().is_some_and(|m| m.starts_with("audio")) || content_type.as_deref() == Some("audio") {
+        Some("audio")
+    } else {
+        None
+    }
+}
+
+/// Attributes parsed off an `#EXT-X-STREAM-INF` line.
+#[derive(Default)]
+struct StreamInf {
+    bandwidth: Option<u64>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+fn parse_stream_inf(attrs: &str) -> StreamInf {
+    let mut inf = StreamInf::default();
+    for attr in attrs.split(',') {
+        let attr = attr.trim();
+        if let Some(value) = attr.strip_prefix("BANDWIDTH=") {
+            inf.bandwidth = value.parse().ok();
+        } else if let Some(value) = attr.strip_prefix("RESOLUTION=") {
+            if let Some((w, h)) = value.split_once('x') {
+                inf.width = w.parse().ok();
+                inf.height = h.parse().ok();
+            }
+        }
+    }
+    inf
+}
+
+/// Pull a `KEY="value"` attribute out of a comma-separated attribute list.
+fn parse_quoted_attr(attrs: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}=\"", key);
+    let start = attrs.find(&prefix)? + prefix.len();
+    let end = attrs[start..].find('"')? + start;
+    Some(attrs[start..end].to_string())
+}
+
+/// Resolve a playlist-relative URI (segment or variant sub-playlist) against
+/// the manifest's own URL.
+fn resolve_uri(base: &Url, uri: &str) -> Option<Url> {
+    base.join(uri).ok()
 }
@@ -1,34 +1,111 @@
+mod bilibili;
 mod direct;
+mod opengraph;
+mod registry;
+mod retry;
 mod twitter;
 mod types;
+mod ytdlp;
 
+use bilibili::BilibiliExtractor;
+use opengraph::OpenGraphExtractor;
+pub use registry::{Extractor, ExtractorRegistry};
 pub use types::*;
+pub use ytdlp::{YtDlpExtractor, YtDlpOptions, YtDlpResult};
 
 use crate::config::get_config;
 use url::Url;
 
-/// Extract media information from a URL
+/// Extract media information from a URL. Sites with no dedicated extractor
+/// fall through to `YtDlpExtractor` (which always matches); if that in turn
+/// fails (e.g. yt-dlp isn't installed), a last-resort Open Graph/oEmbed scrape
+/// is tried before the original error is surfaced.
 pub async fn extract_media(url_str: &str) -> Result<MediaInfo, ExtractError> {
-    let url = Url::parse(url_str).map_err(|_| ExtractError::InvalidUrl(url_str.to_string()))?;
-
-    // Check for Twitter/X URLs
-    if twitter::TwitterExtractor::matches(&url) {
-        // Load auth token from config
-        let auth_token = get_config()
-            .ok()
-            .and_then(|c| c.twitter.auth_token);
-        return twitter::TwitterExtractor::extract(url_str, auth_token).await;
+    // Validate the URL shape up front so a malformed input gets a clear error
+    // rather than falling through every extractor's `matches`.
+    Url::parse(url_str).map_err(|_| ExtractError::InvalidUrl(url_str.to_string()))?;
+
+    let config = get_config().unwrap_or_default();
+    let result = match ExtractorRegistry::new(config.twitter.auth_token.clone())
+        .extract(url_str)
+        .await
+    {
+        Ok(info) => Ok(info),
+        Err(err) => OpenGraphExtractor::extract(url_str).await.or(Err(err)),
+    };
+
+    result.map(|mut info| {
+        info.formats = select_formats(
+            info.formats,
+            &config.quality,
+            &config.format,
+            config.codec.as_deref(),
+        );
+        info
+    })
+}
+
+/// Like `extract_media`, but goes straight through yt-dlp instead of the
+/// crate's hand-written extractors, exposing the richer `youtube_dl`-style
+/// options `extract_media_ytdlp` takes from the frontend. When `opts` names
+/// no cookie file, the existing logged-in Bilibili cookie (if any) is passed
+/// along as one, so a yt-dlp URL that needs it doesn't have to be re-entered.
+pub async fn extract_media_ytdlp(url: &str, mut opts: YtDlpOptions) -> Result<YtDlpResult, ExtractError> {
+    if opts.cookie_file.is_none() {
+        let config = get_config().unwrap_or_default();
+        if let Some(cookie) = config.bilibili.cookie {
+            if let Ok(path) = write_netscape_cookie_file("bilibili.com", &cookie) {
+                opts.cookie_file = Some(path);
+            }
+        }
     }
 
-    // Check for direct file URLs
-    if direct::DirectExtractor::matches(&url) {
-        return direct::DirectExtractor::extract(url_str).await;
+    YtDlpExtractor::extract_with_options(url, &opts).await
+}
+
+/// Write `raw_cookie` (a `"k=v; k2=v2"` header-style string, as stored for
+/// Bilibili) out as a Netscape-format cookie file yt-dlp's `--cookies` can
+/// read, scoped to `domain`.
+fn write_netscape_cookie_file(domain: &str, raw_cookie: &str) -> std::io::Result<String> {
+    let mut contents = String::from("# Netscape HTTP Cookie File\n");
+    for pair in raw_cookie.split(';') {
+        let pair = pair.trim();
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        contents.push_str(&format!(
+            ".{}\tTRUE\t/\tTRUE\t0\t{}\t{}\n",
+            domain,
+            key.trim(),
+            value.trim()
+        ));
     }
 
-    // TODO: Add more extractors here
-    // - Bilibili
-    // - Xiaoyuzhou
-    // - etc.
+    let path = std::env::temp_dir().join(format!("vget-{}-cookies.txt", domain));
+    std::fs::write(&path, contents)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Extract every part of a multi-part (分P) Bilibili upload as its own
+/// `MediaInfo`, so a caller can download the whole upload at once. Any other
+/// site falls back to `extract_media`'s single item, wrapped in a one-element
+/// Vec, since only Bilibili's API exposes a page list today.
+pub async fn extract_playlist(url_str: &str) -> Result<Vec<MediaInfo>, ExtractError> {
+    let parsed = Url::parse(url_str).map_err(|_| ExtractError::InvalidUrl(url_str.to_string()))?;
+
+    if BilibiliExtractor::matches(&parsed) {
+        let config = get_config().unwrap_or_default();
+        let mut infos = BilibiliExtractor::extract_playlist(url_str).await?;
+        for info in &mut infos {
+            info.formats = select_formats(
+                std::mem::take(&mut info.formats),
+                &config.quality,
+                &config.format,
+                config.codec.as_deref(),
+            );
+        }
+        return Ok(infos);
+    }
 
-    Err(ExtractError::NoExtractor(url_str.to_string()))
+    Ok(vec![extract_media(url_str).await?])
 }
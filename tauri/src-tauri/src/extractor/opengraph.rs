@@ -0,0 +1,142 @@
+use super::direct::{AUDIO_EXTENSIONS, IMAGE_EXTENSIONS, VIDEO_EXTENSIONS};
+use super::types::*;
+use std::collections::HashMap;
+
+/// Last-resort extractor for any page without a dedicated site handler:
+/// scrapes Open Graph / Twitter Card meta tags for a directly playable media
+/// URL, falling back to an oEmbed endpoint discovered via a
+/// `<link rel="alternate" type="application/json+oembed">` tag.
+pub struct OpenGraphExtractor;
+
+impl OpenGraphExtractor {
+    pub async fn extract(url: &str) -> Result<MediaInfo, ExtractError> {
+        let client = reqwest::Client::builder()
+            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+            .build()
+            .unwrap_or_default();
+
+        let html = client
+            .get(url)
+            .send()
+            .await?
+            .text()
+            .await
+            .map_err(|e| ExtractError::Parse(format!("Failed to read page body: {}", e)))?;
+
+        let title = meta_content(&html, "og:title");
+        let site_name = meta_content(&html, "og:site_name");
+        let thumbnail = meta_content(&html, "og:image");
+
+        let media_url = meta_content(&html, "og:video:secure_url")
+            .or_else(|| meta_content(&html, "og:video:url"))
+            .or_else(|| meta_content(&html, "og:video"))
+            .or_else(|| meta_content(&html, "twitter:player:stream"));
+
+        let media_url = match media_url {
+            Some(u) => Some(u),
+            None => Self::discover_oembed_media_url(&client, &html).await,
+        };
+
+        let Some(media_url) = media_url else {
+            return Err(ExtractError::NotAvailable);
+        };
+
+        let ext = media_url
+            .rsplit('/')
+            .next()
+            .unwrap_or("")
+            .rsplit('.')
+            .next()
+            .unwrap_or("")
+            .split(['?', '#'])
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+
+        let media_type = if VIDEO_EXTENSIONS.contains(&ext.as_str()) {
+            MediaType::Video
+        } else if AUDIO_EXTENSIONS.contains(&ext.as_str()) {
+            MediaType::Audio
+        } else if IMAGE_EXTENSIONS.contains(&ext.as_str()) {
+            MediaType::Image
+        } else {
+            MediaType::Video
+        };
+
+        Ok(MediaInfo {
+            id: url.to_string(),
+            title: title.unwrap_or_else(|| url.to_string()),
+            uploader: site_name,
+            thumbnail,
+            duration: None,
+            media_type,
+            formats: vec![Format {
+                id: "opengraph".to_string(),
+                url: media_url,
+                ext,
+                quality: None,
+                width: None,
+                height: None,
+                filesize: None,
+                audio_url: None,
+                protocol: Some("https".to_string()),
+                headers: HashMap::new(),
+                segments: Vec::new(),
+                key_uri: None,
+                key_iv: None,
+            }],
+            media_items: Vec::new(),
+        })
+    }
+
+    /// Follow a discovered `application/json+oembed` link and read its
+    /// `url` field, if present.
+    async fn discover_oembed_media_url(client: &reqwest::Client, html: &str) -> Option<String> {
+        let oembed_url = oembed_link(html)?;
+        let body: serde_json::Value = client.get(&oembed_url).send().await.ok()?.json().await.ok()?;
+        body.get("url").and_then(|v| v.as_str()).map(|s| s.to_string())
+    }
+}
+
+/// Pull `content="..."` out of a `<meta property="{name}" ...>` or
+/// `<meta name="{name}" ...>` tag, whichever attribute order the page uses.
+fn meta_content(html: &str, name: &str) -> Option<String> {
+    for marker in [format!("property=\"{}\"", name), format!("name=\"{}\"", name)] {
+        if let Some(idx) = html.find(&marker) {
+            let tag_start = html[..idx].rfind('<')?;
+            let tag_end = html[idx..].find('>').map(|e| idx + e)?;
+            let tag = &html[tag_start..tag_end];
+            if let Some(content) = attr_value(tag, "content") {
+                return Some(html_unescape(&content));
+            }
+        }
+    }
+    None
+}
+
+/// Pull `href="..."` out of the first `<link rel="alternate"
+/// type="application/json+oembed">` tag.
+fn oembed_link(html: &str) -> Option<String> {
+    for chunk in html.split("<link") {
+        if chunk.contains("alternate") && chunk.contains("application/json+oembed") {
+            let tag_end = chunk.find('>').unwrap_or(chunk.len());
+            return attr_value(&chunk[..tag_end], "href");
+        }
+    }
+    None
+}
+
+fn attr_value(tag: &str, attr: &str) -> Option<String> {
+    let prefix = format!("{}=\"", attr);
+    let start = tag.find(&prefix)? + prefix.len();
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+fn html_unescape(s: &str) -> String {
+    s.replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+}
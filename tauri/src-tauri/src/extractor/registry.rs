@@ -0,0 +1,91 @@
+use super::types::*;
+use super::{
+    bilibili::BilibiliExtractor, direct::DirectExtractor, twitter::TwitterExtractor,
+    ytdlp::YtDlpExtractor,
+};
+use async_trait::async_trait;
+use url::Url;
+
+/// A pluggable site handler: claims the URLs it knows how to handle, then
+/// resolves one into `MediaInfo`. New sites are added by implementing this
+/// trait and registering an instance in `ExtractorRegistry::new`, without
+/// touching the downloader or any other extractor.
+#[async_trait]
+pub trait Extractor: Send + Sync {
+    /// Whether this extractor can handle the given URL.
+    fn matches(&self, url: &str) -> bool;
+
+    /// Fetch and parse media info for a URL this extractor matched.
+    async fn extract(&self, url: &str) -> Result<MediaInfo, ExtractError>;
+}
+
+struct TwitterAdapter {
+    auth_token: Option<String>,
+}
+
+#[async_trait]
+impl Extractor for TwitterAdapter {
+    fn matches(&self, url: &str) -> bool {
+        Url::parse(url).map(|u| TwitterExtractor::matches(&u)).unwrap_or(false)
+    }
+
+    async fn extract(&self, url: &str) -> Result<MediaInfo, ExtractError> {
+        TwitterExtractor::extract(url, self.auth_token.clone()).await
+    }
+}
+
+struct BilibiliAdapter;
+
+#[async_trait]
+impl Extractor for BilibiliAdapter {
+    fn matches(&self, url: &str) -> bool {
+        Url::parse(url).map(|u| BilibiliExtractor::matches(&u)).unwrap_or(false)
+    }
+
+    async fn extract(&self, url: &str) -> Result<MediaInfo, ExtractError> {
+        BilibiliExtractor::extract(url).await
+    }
+}
+
+struct DirectAdapter;
+
+#[async_trait]
+impl Extractor for DirectAdapter {
+    fn matches(&self, url: &str) -> bool {
+        Url::parse(url).map(|u| DirectExtractor::matches(&u)).unwrap_or(false)
+    }
+
+    async fn extract(&self, url: &str) -> Result<MediaInfo, ExtractError> {
+        DirectExtractor::extract(url).await
+    }
+}
+
+/// Holds every known `Extractor` and dispatches a URL to the first one that
+/// claims it, in registration order.
+pub struct ExtractorRegistry {
+    extractors: Vec<Box<dyn Extractor>>,
+}
+
+impl ExtractorRegistry {
+    pub fn new(twitter_auth_token: Option<String>) -> Self {
+        Self {
+            extractors: vec![
+                Box::new(TwitterAdapter { auth_token: twitter_auth_token }),
+                Box::new(BilibiliAdapter),
+                Box::new(DirectAdapter),
+                // Catch-all: shells out to yt-dlp for any site with no native extractor.
+                Box::new(YtDlpExtractor),
+            ],
+        }
+    }
+
+    pub async fn extract(&self, url_str: &str) -> Result<MediaInfo, ExtractError> {
+        for extractor in &self.extractors {
+            if extractor.matches(url_str) {
+                return extractor.extract(url_str).await;
+            }
+        }
+
+        Err(ExtractError::NoExtractor(url_str.to_string()))
+    }
+}
@@ -0,0 +1,95 @@
+use super::types::ExtractError;
+use reqwest::{Response, StatusCode};
+use std::time::Duration;
+
+/// Attempt/backoff parameters for [`send_with_retry`], sourced from
+/// `ServerConfig` so an operator can tune them without a rebuild.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+        }
+    }
+}
+
+impl From<&crate::config::ServerConfig> for RetryConfig {
+    fn from(server: &crate::config::ServerConfig) -> Self {
+        Self {
+            max_attempts: server.retry_max_attempts,
+            initial_backoff_ms: server.retry_initial_backoff_ms,
+        }
+    }
+}
+
+/// Send a request built fresh by `build` on every attempt (a `RequestBuilder`
+/// can't be reused once consumed by `send`), retrying with exponential
+/// backoff (factor 2) and jitter on connection/timeout errors and `429`/`5xx`
+/// responses. A `Retry-After` header on such a response overrides the
+/// computed backoff. Any other status — including every `4xx` but `429` — is
+/// returned immediately, as is the final attempt's result once
+/// `config.max_attempts` is exhausted.
+pub async fn send_with_retry<F>(build: F, config: &RetryConfig) -> Result<Response, ExtractError>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let mut attempt = 0u32;
+    let mut backoff_ms = config.initial_backoff_ms;
+
+    loop {
+        attempt += 1;
+
+        match build().send().await {
+            Ok(resp) => {
+                let status = resp.status();
+                if status.is_success() || !is_retryable_status(status) || attempt >= config.max_attempts {
+                    return Ok(resp);
+                }
+                let wait = retry_after(&resp).unwrap_or_else(|| Duration::from_millis(with_jitter(backoff_ms)));
+                tokio::time::sleep(wait).await;
+            }
+            Err(err) => {
+                if !is_retryable_error(&err) || attempt >= config.max_attempts {
+                    return Err(ExtractError::Network(err));
+                }
+                tokio::time::sleep(Duration::from_millis(with_jitter(backoff_ms))).await;
+            }
+        }
+
+        backoff_ms *= 2;
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn is_retryable_error(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout()
+}
+
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Add up to 20% random jitter to `base_ms` so concurrent retries don't all
+/// wake up on the same instant.
+fn with_jitter(base_ms: u64) -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let jitter_pct = (nanos % 20) as u64;
+    base_ms + (base_ms * jitter_pct / 100)
+}
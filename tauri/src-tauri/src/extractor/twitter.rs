@@ -1,18 +1,34 @@
 use super::types::*;
 use regex::Regex;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, COOKIE, USER_AGENT};
+use reqwest::StatusCode;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::LazyLock;
 use url::Url;
 
-const BEARER_TOKEN: &str = "AAAAAAAAAAAAAAAAAAAAANRILgAAAAAAnNwIzUejRCOuH5E6I8xnZz4puTs=1Zv7ttfk8LF81IUq16cHjhLTvJu4FA33AGWWjCpTnA";
+/// Bearer tokens to try, in order, for guest (unauthenticated) requests.
+/// X rate-limits/flags individual tokens independently, so a 403/429 on the
+/// `TweetResultByRestId` query rotates to the next one rather than failing
+/// the whole extraction outright. Guest-token activation uses the current
+/// token but does not itself rotate on a 403/429, and the Spaces and
+/// cookie-authenticated requests always use the first token.
+const BEARER_TOKENS: &[&str] = &[
+    "AAAAAAAAAAAAAAAAAAAAANRILgAAAAAAnNwIzUejRCOuH5E6I8xnZz4puTs=1Zv7ttfk8LF81IUq16cHjhLTvJu4FA33AGWWjCpTnA",
+    "AAAAAAAAAAAAAAAAAAAAAFQODgEAAAAAVHTp76lzh3rFzcHbmHVvQxYYpTw%3DckAlMINMjmCwxUcaXbAN4XqJVdgMJehqs2QCwZi0cs5QYRKtw9",
+];
 const GUEST_TOKEN_URL: &str = "https://api.x.com/1.1/guest/activate.json";
 const GRAPHQL_URL: &str = "https://x.com/i/api/graphql/2ICDjqPd81tulZcYrtpTuQ/TweetResultByRestId";
 const SYNDICATION_URL: &str = "https://cdn.syndication.twimg.com/tweet-result";
+const AUDIO_SPACE_GRAPHQL_URL: &str = "https://x.com/i/api/graphql/CGLvAzKL3P5m6vDNZtlCNg/AudioSpaceById";
+const LIVE_VIDEO_STREAM_STATUS_URL: &str = "https://x.com/i/api/1.1/live_video_stream/status";
 
 static URL_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?:twitter\.com|x\.com)/(?:[^/]+)/status/(\d+)").unwrap());
 
+static SPACE_URL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:twitter\.com|x\.com)/i/spaces/(\w+)").unwrap());
+
 static RESOLUTION_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"/(\d+)x(\d+)/").unwrap());
 
@@ -20,6 +36,8 @@ pub struct TwitterExtractor {
     client: reqwest::Client,
     auth_token: Option<String>,
     csrf_token: Option<String>,
+    guest_token: Option<String>,
+    bearer_idx: usize,
 }
 
 impl TwitterExtractor {
@@ -31,16 +49,36 @@ impl TwitterExtractor {
                 .unwrap(),
             auth_token,
             csrf_token: None,
+            guest_token: None,
+            bearer_idx: 0,
+        }
+    }
+
+    /// The bearer token currently in rotation.
+    fn current_bearer(&self) -> &'static str {
+        BEARER_TOKENS[self.bearer_idx]
+    }
+
+    /// Drop the cached guest token (it was issued against the bearer we're
+    /// abandoning) and move to the next bearer token, if any are left.
+    /// Returns `false` once the rotation is exhausted.
+    fn advance_bearer_token(&mut self) -> bool {
+        self.guest_token = None;
+        if self.bearer_idx + 1 < BEARER_TOKENS.len() {
+            self.bearer_idx += 1;
+            true
+        } else {
+            false
         }
     }
 
-    /// Check if URL is a Twitter/X status URL
+    /// Check if URL is a Twitter/X status or Spaces URL
     pub fn matches(url: &Url) -> bool {
         let host = url.host_str().unwrap_or("");
         if !["twitter.com", "x.com", "mobile.twitter.com", "mobile.x.com"].contains(&host) {
             return false;
         }
-        URL_REGEX.is_match(url.as_str())
+        URL_REGEX.is_match(url.as_str()) || SPACE_URL_REGEX.is_match(url.as_str())
     }
 
     /// Extract media info from Twitter URL
@@ -50,6 +88,11 @@ impl TwitterExtractor {
     }
 
     async fn do_extract(&mut self, url_str: &str) -> Result<MediaInfo, ExtractError> {
+        if let Some(caps) = SPACE_URL_REGEX.captures(url_str) {
+            let space_id = caps.get(1).unwrap().as_str().to_string();
+            return self.fetch_space(&space_id).await;
+        }
+
         // Extract tweet ID
         let caps = URL_REGEX
             .captures(url_str)
@@ -67,15 +110,21 @@ impl TwitterExtractor {
         }
 
         // Fallback to GraphQL with guest token
-        let guest_token = self.fetch_guest_token().await?;
-        self.fetch_from_graphql(tweet_id, &guest_token).await
+        self.fetch_from_graphql(tweet_id).await
     }
 
-    async fn fetch_guest_token(&self) -> Result<String, ExtractError> {
+    /// Activate a guest token against the current bearer, caching it on
+    /// `self` so subsequent calls within this extraction reuse it instead of
+    /// re-activating every time.
+    async fn fetch_guest_token(&mut self) -> Result<String, ExtractError> {
+        if let Some(token) = &self.guest_token {
+            return Ok(token.clone());
+        }
+
         let resp = self
             .client
             .post(GUEST_TOKEN_URL)
-            .header(AUTHORIZATION, format!("Bearer {}", BEARER_TOKEN))
+            .header(AUTHORIZATION, format!("Bearer {}", self.current_bearer()))
             .send()
             .await?;
 
@@ -92,11 +141,13 @@ impl TwitterExtractor {
         }
 
         let data: GuestTokenResponse = resp.json().await?;
+        self.guest_token = Some(data.guest_token.clone());
         Ok(data.guest_token)
     }
 
     async fn fetch_from_syndication(&self, tweet_id: &str) -> Result<MediaInfo, ExtractError> {
-        let url = format!("{}?id={}&token=x", SYNDICATION_URL, tweet_id);
+        let token = compute_syndication_token(tweet_id);
+        let url = format!("{}?id={}&token={}", SYNDICATION_URL, tweet_id, token);
 
         let resp = self
             .client
@@ -114,14 +165,14 @@ impl TwitterExtractor {
         }
 
         let data: SyndicationResponse = resp.json().await?;
-        self.parse_syndication_response(&data, tweet_id)
+        self.parse_syndication_response(&data, tweet_id).await
     }
 
-    async fn fetch_from_graphql(
-        &self,
-        tweet_id: &str,
-        guest_token: &str,
-    ) -> Result<MediaInfo, ExtractError> {
+    /// Run the `TweetResultByRestId` GraphQL query with a guest token,
+    /// rotating to the next bearer token (and re-activating a fresh guest
+    /// token under it) whenever X responds 403/429, instead of failing the
+    /// whole extraction on the first flagged/rate-limited token.
+    async fn fetch_from_graphql(&mut self, tweet_id: &str) -> Result<MediaInfo, ExtractError> {
         let (variables, features) = build_graphql_params(tweet_id);
         let url = format!(
             "{}?variables={}&features={}",
@@ -130,25 +181,39 @@ impl TwitterExtractor {
             urlencoding::encode(&features)
         );
 
-        let resp = self
-            .client
-            .get(&url)
-            .header(AUTHORIZATION, format!("Bearer {}", BEARER_TOKEN))
-            .header("x-guest-token", guest_token)
-            .header(CONTENT_TYPE, "application/json")
-            .header(USER_AGENT, "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
-            .send()
-            .await?;
+        loop {
+            let guest_token = self.fetch_guest_token().await?;
+
+            let resp = self
+                .client
+                .get(&url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.current_bearer()))
+                .header("x-guest-token", &guest_token)
+                .header(CONTENT_TYPE, "application/json")
+                .header(USER_AGENT, "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+                .send()
+                .await?;
+
+            if matches!(resp.status(), StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS) {
+                if self.advance_bearer_token() {
+                    continue;
+                }
+                return Err(ExtractError::Parse(format!(
+                    "GraphQL request failed: {}",
+                    resp.status()
+                )));
+            }
 
-        if !resp.status().is_success() {
-            return Err(ExtractError::Parse(format!(
-                "GraphQL request failed: {}",
-                resp.status()
-            )));
-        }
+            if !resp.status().is_success() {
+                return Err(ExtractError::Parse(format!(
+                    "GraphQL request failed: {}",
+                    resp.status()
+                )));
+            }
 
-        let body = resp.text().await?;
-        self.parse_graphql_response(&body, tweet_id)
+            let body = resp.text().await?;
+            return self.parse_graphql_response(&body, tweet_id).await;
+        }
     }
 
     async fn fetch_csrf_token(&mut self) -> Result<(), ExtractError> {
@@ -191,7 +256,7 @@ impl TwitterExtractor {
         );
 
         let mut headers = HeaderMap::new();
-        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", BEARER_TOKEN)).unwrap());
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", BEARER_TOKENS[0])).unwrap());
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36"));
         headers.insert("x-twitter-auth-type", HeaderValue::from_static("OAuth2Session"));
@@ -213,10 +278,190 @@ impl TwitterExtractor {
         }
 
         let body = resp.text().await?;
-        self.parse_graphql_response(&body, tweet_id)
+        self.parse_graphql_response(&body, tweet_id).await
+    }
+
+    /// Archive a live or ended X Space (`x.com/i/spaces/<id>`). Mirrors
+    /// yt-dlp: look up the space's `media_key`/`state` via the GraphQL
+    /// `AudioSpaceById` operation, then resolve that media key to an HLS
+    /// playlist through the `live_video_stream/status` endpoint. A space
+    /// whose metadata reports a state other than `Running`/`Ended` surfaces
+    /// `AuthRequired` so the caller knows to retry once a cookie is
+    /// configured rather than treating it as permanently gone; a non-success
+    /// HTTP status from either request comes back as `ExtractError::Parse`.
+    async fn fetch_space(&mut self, space_id: &str) -> Result<MediaInfo, ExtractError> {
+        let use_auth = self.auth_token.is_some();
+        if use_auth && self.csrf_token.is_none() {
+            self.fetch_csrf_token().await?;
+        }
+        let guest_token = if use_auth { None } else { Some(self.fetch_guest_token().await?) };
+
+        let metadata = self.fetch_space_metadata(space_id, guest_token.as_deref()).await?;
+        let media_key = metadata.media_key.ok_or(ExtractError::NotAvailable)?;
+
+        match metadata.state.as_deref() {
+            Some("Running") | Some("Ended") => {}
+            _ => return Err(ExtractError::AuthRequired),
+        }
+
+        let stream_url = self
+            .fetch_space_stream_url(&media_key, guest_token.as_deref())
+            .await?;
+
+        Ok(MediaInfo {
+            id: space_id.to_string(),
+            title: metadata.title.unwrap_or_else(|| format!("Space {}", space_id)),
+            uploader: None,
+            thumbnail: None,
+            duration: None,
+            media_type: MediaType::Audio,
+            formats: vec![Format {
+                id: "hls".into(),
+                url: stream_url,
+                ext: "m3u8".into(),
+                quality: None,
+                width: None,
+                height: None,
+                filesize: None,
+                audio_url: None,
+                protocol: Some("m3u8".into()),
+                headers: HashMap::new(),
+                segments: Vec::new(),
+                key_uri: None,
+                key_iv: None,
+            }],
+            media_items: Vec::new(),
+        })
     }
 
-    fn parse_syndication_response(
+    /// Run the `AudioSpaceById` GraphQL query for `space_id`, authenticated
+    /// with `guest_token` if given, or with the logged-in cookie/CSRF pair
+    /// otherwise.
+    async fn fetch_space_metadata(
+        &self,
+        space_id: &str,
+        guest_token: Option<&str>,
+    ) -> Result<SpaceMetadata, ExtractError> {
+        let variables = serde_json::json!({
+            "id": space_id,
+            "isMetatagsQuery": false,
+            "withReplays": true,
+            "withListeners": true,
+        });
+        let url = format!(
+            "{}?variables={}",
+            AUDIO_SPACE_GRAPHQL_URL,
+            urlencoding::encode(&variables.to_string())
+        );
+
+        let resp = self.client.get(&url).headers(self.space_headers(guest_token)).send().await?;
+
+        if !resp.status().is_success() {
+            return Err(ExtractError::Parse(format!("AudioSpaceById request failed: {}", resp.status())));
+        }
+
+        let body = resp.text().await?;
+        let parsed: SpaceGraphQLResponse = serde_json::from_str(&body).map_err(|e| ExtractError::Parse(e.to_string()))?;
+        parsed.data.audio_space.map(|a| a.metadata).ok_or(ExtractError::NotAvailable)
+    }
+
+    /// Resolve a space's `media_key` to its HLS `.m3u8` URL via the
+    /// `live_video_stream/status` endpoint.
+    async fn fetch_space_stream_url(
+        &self,
+        media_key: &str,
+        guest_token: Option<&str>,
+    ) -> Result<String, ExtractError> {
+        let url = format!("{}/{}", LIVE_VIDEO_STREAM_STATUS_URL, media_key);
+
+        let resp = self.client.get(&url).headers(self.space_headers(guest_token)).send().await?;
+
+        if !resp.status().is_success() {
+            return Err(ExtractError::Parse(format!("live_video_stream status request failed: {}", resp.status())));
+        }
+
+        let body: LiveVideoStreamStatus = resp.json().await?;
+        body.source
+            .and_then(|s| s.location)
+            .ok_or(ExtractError::NotAvailable)
+    }
+
+    /// Headers shared by both Spaces requests: bearer + guest token when
+    /// unauthenticated, or the logged-in cookie/CSRF pair when `auth_token`
+    /// is set (mirroring `fetch_from_graphql_auth`'s header set).
+    fn space_headers(&self, guest_token: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", BEARER_TOKENS[0])).unwrap());
+        headers.insert(USER_AGENT, HeaderValue::from_static("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36"));
+
+        if let Some(guest_token) = guest_token {
+            headers.insert("x-guest-token", HeaderValue::from_str(guest_token).unwrap());
+        } else if let (Some(auth_token), Some(csrf_token)) = (&self.auth_token, &self.csrf_token) {
+            headers.insert("x-twitter-auth-type", HeaderValue::from_static("OAuth2Session"));
+            headers.insert("x-twitter-active-user", HeaderValue::from_static("yes"));
+            headers.insert("x-csrf-token", HeaderValue::from_str(csrf_token).unwrap());
+            headers.insert(
+                COOKIE,
+                HeaderValue::from_str(&format!("auth_token={}; ct0={}", auth_token, csrf_token)).unwrap(),
+            );
+        }
+
+        headers
+    }
+
+    /// Fetch an HLS master playlist and split it into one `Format` per
+    /// quality rung by reading each `#EXT-X-STREAM-INF` line's
+    /// `RESOLUTION=WxH` attribute and the URI line that follows it. Returns
+    /// an empty `Vec` on any fetch/parse failure, leaving the caller's own
+    /// master-playlist `Format` as the fallback.
+    async fn fetch_hls_variant_formats(&self, master_url: &str) -> Vec<Format> {
+        let Ok(base) = Url::parse(master_url) else {
+            return Vec::new();
+        };
+        let Ok(resp) = self.client.get(master_url).send().await else {
+            return Vec::new();
+        };
+        let Ok(body) = resp.text().await else {
+            return Vec::new();
+        };
+
+        let mut formats = Vec::new();
+        let mut pending_resolution: Option<(u32, u32)> = None;
+        for line in body.lines() {
+            let line = line.trim();
+            if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+                pending_resolution = attrs
+                    .split(',')
+                    .find_map(|attr| attr.trim().strip_prefix("RESOLUTION="))
+                    .and_then(|res| res.split_once('x'))
+                    .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)));
+            } else if !line.is_empty() && !line.starts_with('#') {
+                let Ok(variant_url) = base.join(line) else {
+                    continue;
+                };
+                let (width, height) = pending_resolution.take().unwrap_or((0, 0));
+                formats.push(Format {
+                    id: format!("hls_{}", formats.len()),
+                    url: variant_url.to_string(),
+                    ext: "m3u8".into(),
+                    quality: if height > 0 { Some(format!("{}p", height)) } else { None },
+                    width: if width > 0 { Some(width) } else { None },
+                    height: if height > 0 { Some(height) } else { None },
+                    filesize: None,
+                    audio_url: None,
+                    protocol: Some("m3u8".into()),
+                    headers: HashMap::new(),
+                    segments: Vec::new(),
+                    key_uri: None,
+                    key_iv: None,
+                });
+            }
+        }
+
+        formats
+    }
+
+    async fn parse_syndication_response(
         &self,
         data: &SyndicationResponse,
         tweet_id: &str,
@@ -225,14 +470,37 @@ impl TwitterExtractor {
         let uploader = data.user.as_ref().map(|u| u.screen_name.clone());
 
         let mut formats = Vec::new();
+        let mut media_items: Vec<MediaItem> = Vec::new();
 
         // Process media_details
         if let Some(media_details) = &data.media_details {
             for media in media_details {
+                let mut item_formats = Vec::new();
+
                 match media.r#type.as_str() {
                     "video" | "animated_gif" => {
                         if let Some(video_info) = &media.video_info {
                             for variant in &video_info.variants {
+                                if variant.content_type == "application/x-mpegURL" {
+                                    item_formats.push(Format {
+                                        id: "hls".into(),
+                                        url: variant.url.clone(),
+                                        ext: "m3u8".into(),
+                                        quality: None,
+                                        width: None,
+                                        height: None,
+                                        filesize: None,
+                                        audio_url: None,
+                                        protocol: Some("m3u8".into()),
+                                        headers: HashMap::new(),
+                                        segments: Vec::new(),
+                                        key_uri: None,
+                                        key_iv: None,
+                                    });
+                                    item_formats.extend(self.fetch_hls_variant_formats(&variant.url).await);
+                                    continue;
+                                }
+
                                 if variant.content_type != "video/mp4" {
                                     continue;
                                 }
@@ -244,7 +512,7 @@ impl TwitterExtractor {
                                     estimate_quality(variant.bitrate)
                                 };
 
-                                formats.push(Format {
+                                item_formats.push(Format {
                                     id: format!("mp4_{}", variant.bitrate.unwrap_or(0)),
                                     url: variant.url.clone(),
                                     ext: "mp4".into(),
@@ -253,6 +521,11 @@ impl TwitterExtractor {
                                     height: if height > 0 { Some(height) } else { None },
                                     filesize: None,
                                     audio_url: None,
+                                    protocol: Some("https".into()),
+                                    headers: HashMap::new(),
+                                    segments: Vec::new(),
+                                    key_uri: None,
+                                    key_iv: None,
                                 });
                             }
                         }
@@ -260,7 +533,7 @@ impl TwitterExtractor {
                     "photo" => {
                         let image_url = get_high_quality_image_url(&media.media_url_https);
                         let ext = get_image_extension(&media.media_url_https);
-                        formats.push(Format {
+                        item_formats.push(Format {
                             id: "photo".into(),
                             url: image_url,
                             ext,
@@ -269,23 +542,45 @@ impl TwitterExtractor {
                             height: media.original_info_height,
                             filesize: None,
                             audio_url: None,
+                            protocol: Some("https".into()),
+                            headers: HashMap::new(),
+                            segments: Vec::new(),
+                            key_uri: None,
+                            key_iv: None,
                         });
                     }
                     _ => {}
                 }
+
+                if item_formats.is_empty() {
+                    continue;
+                }
+
+                let item_media_type = if item_formats.iter().any(|f| f.ext == "mp4" || f.ext == "m3u8") {
+                    MediaType::Video
+                } else {
+                    MediaType::Image
+                };
+                media_items.push(MediaItem {
+                    index: media_items.len(),
+                    media_type: item_media_type,
+                    formats: item_formats.clone(),
+                });
+                formats.extend(item_formats);
             }
         }
 
         // Also check video field for single video tweets
         if formats.is_empty() {
             if let Some(video) = &data.video {
+                let mut item_formats = Vec::new();
                 for variant in &video.variants {
                     if variant.r#type != "video/mp4" {
                         continue;
                     }
                     if let Some(src) = &variant.src {
                         let (width, height) = extract_resolution(src);
-                        formats.push(Format {
+                        item_formats.push(Format {
                             id: "mp4_direct".into(),
                             url: src.clone(),
                             ext: "mp4".into(),
@@ -294,9 +589,22 @@ impl TwitterExtractor {
                             height: if height > 0 { Some(height) } else { None },
                             filesize: None,
                             audio_url: None,
+                            protocol: Some("https".into()),
+                            headers: HashMap::new(),
+                            segments: Vec::new(),
+                            key_uri: None,
+                            key_iv: None,
                         });
                     }
                 }
+                if !item_formats.is_empty() {
+                    media_items.push(MediaItem {
+                        index: media_items.len(),
+                        media_type: MediaType::Video,
+                        formats: item_formats.clone(),
+                    });
+                    formats.extend(item_formats);
+                }
             }
         }
 
@@ -312,7 +620,7 @@ impl TwitterExtractor {
         });
 
         // Determine media type
-        let media_type = if formats.iter().any(|f| f.ext == "mp4") {
+        let media_type = if formats.iter().any(|f| f.ext == "mp4" || f.ext == "m3u8") {
             MediaType::Video
         } else {
             MediaType::Image
@@ -326,10 +634,11 @@ impl TwitterExtractor {
             duration: None,
             media_type,
             formats,
+            media_items,
         })
     }
 
-    fn parse_graphql_response(
+    async fn parse_graphql_response(
         &self,
         body: &str,
         tweet_id: &str,
@@ -370,15 +679,21 @@ impl TwitterExtractor {
             .and_then(|c| c.user_results.result.as_ref())
             .map(|u| u.legacy.screen_name.clone());
 
-        let extended_entities = legacy
-            .extended_entities
-            .as_ref()
-            .ok_or(ExtractError::NotAvailable)?;
+        // No native attachments: the tweet may still embed an external video
+        // or rich link card (YouTube, Vimeo, ...) via its `card`/`entities`,
+        // in which case we surface that as an embed result rather than
+        // failing outright.
+        let Some(extended_entities) = legacy.extended_entities.as_ref() else {
+            return build_card_embed(legacy, result.card.as_ref(), tweet_id, title, uploader);
+        };
 
         let mut formats = Vec::new();
+        let mut media_items: Vec<MediaItem> = Vec::new();
         let mut duration: Option<u64> = None;
 
         for media in &extended_entities.media {
+            let mut item_formats = Vec::new();
+
             match media.r#type.as_str() {
                 "video" | "animated_gif" => {
                     if let Some(video_info) = &media.video_info {
@@ -387,6 +702,26 @@ impl TwitterExtractor {
                         }
 
                         for variant in &video_info.variants {
+                            if variant.content_type == "application/x-mpegURL" {
+                                item_formats.push(Format {
+                                    id: "hls".into(),
+                                    url: variant.url.clone(),
+                                    ext: "m3u8".into(),
+                                    quality: None,
+                                    width: None,
+                                    height: None,
+                                    filesize: None,
+                                    audio_url: None,
+                                    protocol: Some("m3u8".into()),
+                                    headers: HashMap::new(),
+                                    segments: Vec::new(),
+                                    key_uri: None,
+                                    key_iv: None,
+                                });
+                                item_formats.extend(self.fetch_hls_variant_formats(&variant.url).await);
+                                continue;
+                            }
+
                             if variant.content_type != "video/mp4" {
                                 continue;
                             }
@@ -398,7 +733,7 @@ impl TwitterExtractor {
                                 estimate_quality(variant.bitrate)
                             };
 
-                            formats.push(Format {
+                            item_formats.push(Format {
                                 id: format!("mp4_{}", variant.bitrate.unwrap_or(0)),
                                 url: variant.url.clone(),
                                 ext: "mp4".into(),
@@ -407,6 +742,11 @@ impl TwitterExtractor {
                                 height: if height > 0 { Some(height) } else { None },
                                 filesize: None,
                                 audio_url: None,
+                                protocol: Some("https".into()),
+                                headers: HashMap::new(),
+                                segments: Vec::new(),
+                                key_uri: None,
+                                key_iv: None,
                             });
                         }
                     }
@@ -414,7 +754,7 @@ impl TwitterExtractor {
                 "photo" => {
                     let image_url = get_high_quality_image_url(&media.media_url_https);
                     let ext = get_image_extension(&media.media_url_https);
-                    formats.push(Format {
+                    item_formats.push(Format {
                         id: "photo".into(),
                         url: image_url,
                         ext,
@@ -423,10 +763,31 @@ impl TwitterExtractor {
                         height: media.original_info.as_ref().map(|i| i.height),
                         filesize: None,
                         audio_url: None,
+                        protocol: Some("https".into()),
+                        headers: HashMap::new(),
+                        segments: Vec::new(),
+                        key_uri: None,
+                        key_iv: None,
                     });
                 }
                 _ => {}
             }
+
+            if item_formats.is_empty() {
+                continue;
+            }
+
+            let item_media_type = if item_formats.iter().any(|f| f.ext == "mp4" || f.ext == "m3u8") {
+                MediaType::Video
+            } else {
+                MediaType::Image
+            };
+            media_items.push(MediaItem {
+                index: media_items.len(),
+                media_type: item_media_type,
+                formats: item_formats.clone(),
+            });
+            formats.extend(item_formats);
         }
 
         if formats.is_empty() {
@@ -440,7 +801,7 @@ impl TwitterExtractor {
             height_b.cmp(&height_a)
         });
 
-        let media_type = if formats.iter().any(|f| f.ext == "mp4") {
+        let media_type = if formats.iter().any(|f| f.ext == "mp4" || f.ext == "m3u8") {
             MediaType::Video
         } else {
             MediaType::Image
@@ -454,6 +815,7 @@ impl TwitterExtractor {
             duration,
             media_type,
             formats,
+            media_items,
         })
     }
 }
@@ -493,6 +855,60 @@ fn build_graphql_params(tweet_id: &str) -> (String, String) {
     (variables.to_string(), features.to_string())
 }
 
+/// Derive the syndication CDN's `token` query param for `tweet_id`: render
+/// `(id / 1e15) * pi` in base-36 and strip every `'0'` and `'.'` from the
+/// result. Falls back to the old static `"x"` token if `tweet_id` somehow
+/// isn't numeric, since that's still accepted for some tweets.
+fn compute_syndication_token(tweet_id: &str) -> String {
+    let Ok(id) = tweet_id.parse::<f64>() else {
+        return "x".to_string();
+    };
+
+    let value = (id / 1e15) * std::f64::consts::PI;
+    to_base36(value, 11).chars().filter(|&c| c != '0' && c != '.').collect()
+}
+
+/// Render `value` in base-36: the integer part via repeated `% 36` / `/ 36`,
+/// the fractional part (to `frac_digits` places) via repeated `* 36` with the
+/// floor taken each step.
+fn to_base36(value: f64, frac_digits: usize) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    let negative = value < 0.0;
+    let value = value.abs();
+    let mut int_part = value.trunc() as u64;
+    let mut frac_part = value.fract();
+
+    let mut int_digits = Vec::new();
+    if int_part == 0 {
+        int_digits.push(DIGITS[0]);
+    } else {
+        while int_part > 0 {
+            int_digits.push(DIGITS[(int_part % 36) as usize]);
+            int_part /= 36;
+        }
+        int_digits.reverse();
+    }
+
+    let mut result = String::new();
+    if negative {
+        result.push('-');
+    }
+    result.push_str(&String::from_utf8(int_digits).unwrap());
+
+    if frac_digits > 0 {
+        result.push('.');
+        for _ in 0..frac_digits {
+            frac_part *= 36.0;
+            let digit = (frac_part.floor() as usize).min(35);
+            result.push(DIGITS[digit] as char);
+            frac_part -= digit as f64;
+        }
+    }
+
+    result
+}
+
 fn truncate_text(s: &str, max_len: usize) -> String {
     let s = s.replace('\n', " ");
     let chars: Vec<char> = s.chars().collect();
@@ -503,6 +919,83 @@ fn truncate_text(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Build an embed `MediaInfo` for a tweet with no native attachments: the
+/// canonical external URL comes from the tweet's first `entities.urls`
+/// entry (falling back to the card's own `card_url` binding), with a title
+/// and thumbnail pulled from the card's bindings where present. The
+/// resulting `Format`'s `url` points at the external resource itself (e.g. a
+/// YouTube link) rather than a playable file, so callers are expected to
+/// hand it back to `extract_media` for the linked site's own extractor to
+/// resolve.
+fn build_card_embed(
+    legacy: &GraphQLLegacy,
+    card: Option<&GraphQLCard>,
+    tweet_id: &str,
+    tweet_title: String,
+    uploader: Option<String>,
+) -> Result<MediaInfo, ExtractError> {
+    let expanded_url = legacy
+        .entities
+        .as_ref()
+        .and_then(|e| e.urls.first())
+        .map(|u| u.expanded_url.clone())
+        .or_else(|| card.and_then(|c| card_binding_value(c, "card_url")).map(|s| s.to_string()))
+        .ok_or(ExtractError::NotAvailable)?;
+
+    let title = card
+        .and_then(|c| card_binding_value(c, "title"))
+        .map(|s| s.to_string())
+        .unwrap_or(tweet_title);
+
+    let thumbnail = card
+        .and_then(|c| {
+            card_binding_image(c, "photo_image_full_size").or_else(|| card_binding_image(c, "player_image"))
+        })
+        .map(|s| s.to_string());
+
+    Ok(MediaInfo {
+        id: tweet_id.to_string(),
+        title,
+        uploader,
+        thumbnail,
+        duration: None,
+        media_type: MediaType::Video,
+        formats: vec![Format {
+            id: "embed".into(),
+            url: expanded_url,
+            ext: String::new(),
+            quality: None,
+            width: None,
+            height: None,
+            filesize: None,
+            audio_url: None,
+            protocol: Some("embed".into()),
+            headers: HashMap::new(),
+            segments: Vec::new(),
+            key_uri: None,
+            key_iv: None,
+        }],
+        media_items: Vec::new(),
+    })
+}
+
+fn card_binding_value<'a>(card: &'a GraphQLCard, key: &str) -> Option<&'a str> {
+    card.legacy
+        .binding_values
+        .iter()
+        .find(|b| b.key == key)
+        .and_then(|b| b.value.string_value.as_deref())
+}
+
+fn card_binding_image<'a>(card: &'a GraphQLCard, key: &str) -> Option<&'a str> {
+    card.legacy
+        .binding_values
+        .iter()
+        .find(|b| b.key == key)
+        .and_then(|b| b.value.image_value.as_ref())
+        .map(|i| i.url.as_str())
+}
+
 fn extract_resolution(url: &str) -> (u32, u32) {
     if let Some(caps) = RESOLUTION_REGEX.captures(url) {
         let width = caps.get(1).and_then(|m| m.as_str().parse().ok()).unwrap_or(0);
@@ -630,6 +1123,37 @@ struct GraphQLResult {
     core: Option<GraphQLCore>,
     tweet: Option<Box<GraphQLResult>>,
     reason: Option<String>,
+    card: Option<GraphQLCard>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLCard {
+    legacy: GraphQLCardLegacy,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLCardLegacy {
+    #[serde(default)]
+    binding_values: Vec<GraphQLBindingValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLBindingValue {
+    key: String,
+    value: GraphQLBindingValueValue,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLBindingValueValue {
+    #[serde(default)]
+    string_value: Option<String>,
+    #[serde(default)]
+    image_value: Option<GraphQLImageValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLImageValue {
+    url: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -656,6 +1180,18 @@ struct GraphQLUserLegacy {
 struct GraphQLLegacy {
     full_text: String,
     extended_entities: Option<GraphQLExtendedEntities>,
+    entities: Option<GraphQLEntities>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLEntities {
+    #[serde(default)]
+    urls: Vec<GraphQLUrlEntity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLUrlEntity {
+    expanded_url: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -692,3 +1228,73 @@ struct GraphQLVariant {
     content_type: String,
     url: String,
 }
+
+// AudioSpaceById / live_video_stream response structs
+#[derive(Debug, Deserialize)]
+struct SpaceGraphQLResponse {
+    data: SpaceGraphQLData,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpaceGraphQLData {
+    #[serde(rename = "audioSpace")]
+    audio_space: Option<SpaceAudioSpace>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpaceAudioSpace {
+    metadata: SpaceMetadata,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpaceMetadata {
+    #[serde(default)]
+    media_key: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LiveVideoStreamStatus {
+    source: Option<LiveVideoStreamSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LiveVideoStreamSource {
+    location: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_base36_renders_zero() {
+        assert_eq!(to_base36(0.0, 3), "0.000");
+    }
+
+    #[test]
+    fn to_base36_renders_known_integer() {
+        assert_eq!(to_base36(36.0, 0), "10");
+    }
+
+    #[test]
+    fn to_base36_renders_negative_values() {
+        assert_eq!(to_base36(-36.0, 0), "-10");
+    }
+
+    #[test]
+    fn compute_syndication_token_falls_back_for_non_numeric_id() {
+        assert_eq!(compute_syndication_token("not-a-number"), "x");
+    }
+
+    #[test]
+    fn compute_syndication_token_is_deterministic_for_numeric_id() {
+        let token = compute_syndication_token("1234567890123456");
+        assert_eq!(token, compute_syndication_token("1234567890123456"));
+        assert!(!token.contains('0'));
+        assert!(!token.contains('.'));
+    }
+}
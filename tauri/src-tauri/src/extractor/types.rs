@@ -41,8 +41,109 @@ pub struct Format {
     pub filesize: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub audio_url: Option<String>,
+    /// Delivery protocol for this format's `url`: `"https"` for a plain
+    /// progressive file, `"m3u8"` for an HLS playlist, `"dash"` for a DASH
+    /// manifest, `"embed"` for a link to an external resource (e.g. a
+    /// YouTube URL recovered from a tweet card) that needs to be handed back
+    /// to `extract_media` rather than downloaded directly. Defaults to
+    /// `"https"` when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, String>,
+    /// For an HLS media playlist (`protocol: "m3u8"` pointing directly at
+    /// segments rather than a master playlist): the ordered, absolute
+    /// segment URIs, so a downloader doesn't have to re-fetch and re-parse
+    /// the playlist itself.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<String>,
+    /// `#EXT-X-KEY:METHOD=AES-128` URI and IV for decrypting `segments`,
+    /// if the media playlist is encrypted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_iv: Option<String>,
+}
+
+/// Reorder (and, for `container`, filter) `formats` to match the user's
+/// configured preferences, the way `extract_media` applies them to every
+/// extractor's output:
+/// - `quality` is `"best"`, `"worst"`, or a target like `"1080p"`; formats
+///   are sorted by closeness to the target (ties keep the extractor's
+///   original order).
+/// - `container`, if non-empty, keeps only formats whose `ext` matches it —
+///   unless that would drop every format, in which case the restriction is
+///   skipped rather than returning nothing playable.
+/// - `codec`, if given (e.g. `"av1"`, `"hevc"`, `"avc"`), moves formats
+///   whose `quality` label names that codec (as `BilibiliExtractor` embeds
+///   it, e.g. `"1080P [HEVC]"`) to the front.
+pub fn select_formats(
+    formats: Vec<Format>,
+    quality: &str,
+    container: &str,
+    codec: Option<&str>,
+) -> Vec<Format> {
+    let mut formats = formats;
+
+    if !container.is_empty() {
+        let matching: Vec<Format> = formats
+            .iter()
+            .filter(|f| f.ext.eq_ignore_ascii_case(container))
+            .cloned()
+            .collect();
+        if !matching.is_empty() {
+            formats = matching;
+        }
+    }
+
+    let target = QualityTarget::parse(quality);
+    formats.sort_by_key(|f| target.rank(f));
+
+    if let Some(codec) = codec {
+        let codec = codec.to_lowercase();
+        formats.sort_by_key(|f| !format_names_codec(f, &codec));
+    }
+
+    formats
+}
+
+enum QualityTarget {
+    Best,
+    Worst,
+    Height(u32),
+}
+
+impl QualityTarget {
+    fn parse(quality: &str) -> Self {
+        match quality.to_lowercase().as_str() {
+            "worst" => Self::Worst,
+            "" | "best" => Self::Best,
+            other => other
+                .trim_end_matches('p')
+                .parse()
+                .map(Self::Height)
+                .unwrap_or(Self::Best),
+        }
+    }
+
+    /// Lower rank sorts first.
+    fn rank(&self, format: &Format) -> i64 {
+        let height = format.height.unwrap_or(0) as i64;
+        match self {
+            Self::Best => -height,
+            Self::Worst => height,
+            Self::Height(target) => (height - *target as i64).abs(),
+        }
+    }
+}
+
+fn format_names_codec(format: &Format, codec: &str) -> bool {
+    format
+        .quality
+        .as_deref()
+        .unwrap_or("")
+        .to_lowercase()
+        .contains(codec)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,4 +158,20 @@ pub struct MediaInfo {
     pub duration: Option<u64>,
     pub media_type: MediaType,
     pub formats: Vec<Format>,
+    /// For a post with multiple independently-selectable attachments (e.g.
+    /// a four-photo tweet, or a photo+video mix): one entry per attachment,
+    /// each with its own quality ladder, so a caller can address "item #2"
+    /// rather than picking blindly out of the flattened `formats` list.
+    /// Empty for extractors that only ever produce a single attachment.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media_items: Vec<MediaItem>,
+}
+
+/// One independently-selectable attachment within a multi-media post, as
+/// found in `MediaInfo::media_items`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaItem {
+    pub index: usize,
+    pub media_type: MediaType,
+    pub formats: Vec<Format>,
 }
@@ -0,0 +1,249 @@
+use super::registry::Extractor;
+use super::types::*;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Command;
+
+/// Windows flag that stops a console window from flashing behind the app
+/// every time a child process like yt-dlp is spawned.
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Configuration knobs mirroring the subset of options the `youtube_dl`
+/// crate exposes, threaded through to the yt-dlp invocation by
+/// `YtDlpExtractor::extract_with_options`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct YtDlpOptions {
+    /// `--socket-timeout` in seconds.
+    #[serde(default)]
+    pub socket_timeout: Option<u64>,
+    /// Format selector string passed to `-f` (e.g. `"bestvideo+bestaudio"`).
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Path to a Netscape-format cookie file (`--cookies`), e.g. one
+    /// exported alongside the existing Bilibili cookie.
+    #[serde(default)]
+    pub cookie_file: Option<String>,
+    /// Extra arguments appended verbatim after the ones this module builds.
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// Whether `url` names a playlist. When true, `--no-playlist` is
+    /// omitted so yt-dlp's JSON carries every entry instead of just the first.
+    #[serde(default)]
+    pub playlist: bool,
+}
+
+/// `extract_with_options`'s result: either a single item, or — whenever
+/// yt-dlp's JSON carried an `entries` array — every item in the playlist,
+/// kept distinct rather than flattened into one `Vec` the way `extract_all`
+/// does. `YtDlpOptions::playlist` only decides whether `--no-playlist` is
+/// passed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum YtDlpResult {
+    Media(MediaInfo),
+    Playlist { entries: Vec<MediaInfo> },
+}
+
+/// Fallback extractor that shells out to a locally installed `yt-dlp` (or
+/// `youtube-dl`) binary for any site without a native extractor. Always
+/// `matches`, so it must be registered last.
+pub struct YtDlpExtractor;
+
+impl YtDlpExtractor {
+    /// Prefer the yt-dlp build `binary_resolver` has already downloaded and
+    /// cached; fall back to searching PATH for `yt-dlp`, then `youtube-dl`.
+    fn binary() -> Result<String, ExtractError> {
+        if let Some(path) = crate::binary_resolver::resolved_path(crate::binary_resolver::Tool::YtDlp) {
+            return Ok(path.to_string_lossy().into_owned());
+        }
+
+        for candidate in ["yt-dlp", "youtube-dl"] {
+            if Command::new(candidate).arg("--version").output().is_ok() {
+                return Ok(candidate.to_string());
+            }
+        }
+        Err(ExtractError::Parse(
+            "yt-dlp/youtube-dl not found on PATH".into(),
+        ))
+    }
+
+    /// Run `binary` with `args` followed by `url`, suppressing the console
+    /// window flash on Windows, and parse its stdout as yt-dlp JSON.
+    async fn run(binary: String, args: Vec<String>, url: String) -> Result<YtDlpInfo, ExtractError> {
+        let binary_for_spawn = binary.clone();
+
+        let output = tokio::task::spawn_blocking(move || {
+            let mut cmd = Command::new(&binary_for_spawn);
+            cmd.args(&args).arg(&url);
+            #[cfg(windows)]
+            {
+                use std::os::windows::process::CommandExt;
+                cmd.creation_flags(CREATE_NO_WINDOW);
+            }
+            cmd.output()
+        })
+        .await
+        .map_err(|e| ExtractError::Parse(format!("yt-dlp task join error: {}", e)))?
+        .map_err(|e| ExtractError::Parse(format!("Failed to run {}: {}", binary, e)))?;
+
+        if !output.status.success() {
+            return Err(ExtractError::Parse(format!(
+                "{} exited with {}: {}",
+                binary,
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let json_str = String::from_utf8_lossy(&output.stdout);
+        serde_json::from_str(&json_str)
+            .map_err(|e| ExtractError::Parse(format!("Failed to parse yt-dlp JSON: {}", e)))
+    }
+
+    /// Run `--dump-single-json` and parse the result. A playlist URL yields
+    /// one `MediaInfo` per entry; anything else yields a single-element Vec.
+    pub async fn extract_all(url: &str) -> Result<Vec<MediaInfo>, ExtractError> {
+        let binary = Self::binary()?;
+        let args = vec!["--dump-single-json".to_string(), "--no-warnings".to_string()];
+        let parsed = Self::run(binary, args, url.to_string()).await?;
+
+        if let Some(entries) = parsed.entries {
+            Ok(entries.into_iter().map(ytdlp_info_to_media_info).collect())
+        } else {
+            Ok(vec![ytdlp_info_to_media_info(parsed)])
+        }
+    }
+
+    /// Like `extract_all`, but exposes the `youtube_dl`-style configuration
+    /// knobs in `opts` and keeps a playlist result distinct from a single
+    /// item instead of flattening both into one `Vec`.
+    pub async fn extract_with_options(url: &str, opts: &YtDlpOptions) -> Result<YtDlpResult, ExtractError> {
+        let binary = Self::binary()?;
+
+        let mut args = vec!["--dump-single-json".to_string(), "--no-warnings".to_string()];
+        if !opts.playlist {
+            args.push("--no-playlist".to_string());
+        }
+        if let Some(timeout) = opts.socket_timeout {
+            args.push("--socket-timeout".to_string());
+            args.push(timeout.to_string());
+        }
+        if let Some(format) = &opts.format {
+            args.push("-f".to_string());
+            args.push(format.clone());
+        }
+        if let Some(cookie_file) = &opts.cookie_file {
+            args.push("--cookies".to_string());
+            args.push(cookie_file.clone());
+        }
+        args.extend(opts.extra_args.iter().cloned());
+
+        let parsed = Self::run(binary, args, url.to_string()).await?;
+
+        Ok(match parsed.entries {
+            Some(entries) => YtDlpResult::Playlist {
+                entries: entries.into_iter().map(ytdlp_info_to_media_info).collect(),
+            },
+            None => YtDlpResult::Media(ytdlp_info_to_media_info(parsed)),
+        })
+    }
+}
+
+#[async_trait]
+impl Extractor for YtDlpExtractor {
+    fn matches(&self, _url: &str) -> bool {
+        true
+    }
+
+    async fn extract(&self, url: &str) -> Result<MediaInfo, ExtractError> {
+        Self::extract_all(url).await?
+            .into_iter()
+            .next()
+            .ok_or(ExtractError::NotAvailable)
+    }
+}
+
+fn ytdlp_info_to_media_info(info: YtDlpInfo) -> MediaInfo {
+    let formats = info
+        .formats
+        .into_iter()
+        .flatten()
+        .map(ytdlp_format_to_format)
+        .collect::<Vec<_>>();
+
+    let media_type = if formats.iter().any(|f| matches!(f.ext.as_str(), "mp3" | "m4a" | "opus" | "aac")) {
+        MediaType::Audio
+    } else {
+        MediaType::Video
+    };
+
+    MediaInfo {
+        id: info.id.unwrap_or_else(|| "unknown".to_string()),
+        title: info.title.unwrap_or_else(|| "Untitled".to_string()),
+        uploader: info.uploader,
+        thumbnail: info.thumbnail,
+        duration: info.duration.map(|d| d as u64),
+        media_type,
+        formats,
+        media_items: Vec::new(),
+    }
+}
+
+fn ytdlp_format_to_format(f: YtDlpFormat) -> Format {
+    let filesize = f.filesize.or(f.filesize_approx).map(|n| n as u64);
+
+    // Only an explicit "none" video codec marks a format as audio-only (a
+    // missing `vcodec` is treated as video); use that to steer a sensible container extension for pure-audio entries.
+    let is_audio_only = f.vcodec.as_deref().map(|c| c == "none").unwrap_or(false);
+
+    let ext = f.ext.unwrap_or_else(|| if is_audio_only { "m4a".into() } else { "mp4".into() });
+    let protocol = Some(match ext.as_str() {
+        "m3u8" | "m3u8_native" => "m3u8".to_string(),
+        "mpd" | "dash" => "dash".to_string(),
+        _ => "https".to_string(),
+    });
+
+    Format {
+        id: f.format_id.unwrap_or_else(|| "0".to_string()),
+        url: f.url.unwrap_or_default(),
+        ext,
+        quality: f.format_note,
+        width: f.width.map(|w| w as u32),
+        height: f.height.map(|h| h as u32),
+        filesize,
+        audio_url: None,
+        protocol,
+        headers: f.http_headers.unwrap_or_default(),
+        segments: Vec::new(),
+        key_uri: None,
+        key_iv: None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct YtDlpInfo {
+    id: Option<String>,
+    title: Option<String>,
+    uploader: Option<String>,
+    thumbnail: Option<String>,
+    duration: Option<f64>,
+    #[serde(default)]
+    formats: Option<Vec<YtDlpFormat>>,
+    entries: Option<Vec<YtDlpInfo>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YtDlpFormat {
+    format_id: Option<String>,
+    url: Option<String>,
+    ext: Option<String>,
+    format_note: Option<String>,
+    width: Option<i64>,
+    height: Option<i64>,
+    filesize: Option<i64>,
+    filesize_approx: Option<i64>,
+    vcodec: Option<String>,
+    http_headers: Option<HashMap<String, String>>,
+}
@@ -1,8 +1,11 @@
 use ffmpeg_sidecar::command::FfmpegCommand;
 use ffmpeg_sidecar::event::{FfmpegEvent, LogLevel};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 /// Parse ffmpeg time string (HH:MM:SS.microseconds) to seconds
 fn parse_time_to_secs(time_str: &str) -> Option<f32> {
@@ -23,8 +26,37 @@ pub fn ffmpeg_available() -> bool {
     FfmpegCommand::new().print_command().spawn().is_ok()
 }
 
+/// Spawn `cmd`, drain its event stream to completion, and surface the last
+/// error log line on failure (ffmpeg-sidecar doesn't expose a reliable exit
+/// code of its own). Shared by the single-pass helpers below that don't need
+/// per-event progress reporting.
+fn run_ffmpeg_to_completion(cmd: &mut FfmpegCommand) -> Result<(), String> {
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
+    let mut error_msg: Option<String> = None;
+
+    for event in child.iter().expect("Failed to iterate ffmpeg events") {
+        match event {
+            FfmpegEvent::Log(LogLevel::Error, msg) => {
+                eprintln!("[ffmpeg error] {}", msg);
+                error_msg = Some(msg);
+            }
+            FfmpegEvent::Done => break,
+            _ => {}
+        }
+    }
+
+    match error_msg {
+        Some(msg) => Err(msg),
+        None => Ok(()),
+    }
+}
+
 /// Merge separate video and audio files into a single output file.
 /// Uses stream copy (-c copy) for fast merging without re-encoding.
+///
+/// Runs `validate_for_merge` first so a codec/container mismatch that would
+/// make a bare stream-copy fail is caught before ffmpeg ever spawns, rather
+/// than surfacing as an opaque ffmpeg error after the fact.
 pub async fn merge_video_audio(
     video_path: &str,
     audio_path: &str,
@@ -39,6 +71,18 @@ pub async fn merge_video_audio(
         return Err(format!("Audio file not found: {}", audio_path));
     }
 
+    let target_container = Path::new(output_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4");
+    let plan = validate_for_merge(video_path, audio_path, target_container, None).await?;
+    if !plan.copy_video || !plan.copy_audio {
+        return Err(format!(
+            "Cannot stream-copy into {} (video copyable: {}, audio copyable: {})",
+            target_container, plan.copy_video, plan.copy_audio
+        ));
+    }
+
     // Create output directory if needed
     if let Some(parent) = Path::new(output_path).parent() {
         std::fs::create_dir_all(parent)
@@ -111,11 +155,271 @@ pub async fn merge_video_audio(
     Ok(())
 }
 
-/// Get the path to the bundled ffmpeg binary
+// ============ MERGE VALIDATION ============
+
+/// Video codec of a stream classified by `classify_media`, covering the
+/// codecs `merge_video_audio` is actually likely to see in the wild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VideoCodec {
+    H264,
+    H265,
+    Av1,
+    Vp9,
+    Other(String),
+}
+
+impl VideoCodec {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "h264" => Self::H264,
+            "hevc" => Self::H265,
+            "av1" => Self::Av1,
+            "vp9" => Self::Vp9,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// Audio codec counterpart to [`VideoCodec`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioCodec {
+    Aac,
+    Opus,
+    Vorbis,
+    Mp3,
+    Other(String),
+}
+
+impl AudioCodec {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "aac" => Self::Aac,
+            "opus" => Self::Opus,
+            "vorbis" => Self::Vorbis,
+            "mp3" => Self::Mp3,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// A source file's container+codec combination, as classified from its
+/// `MediaInfoResult` by `classify_media`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MediaProfile {
+    Mp4 {
+        video: Option<VideoCodec>,
+        audio: Option<AudioCodec>,
+    },
+    WebM {
+        video: Option<VideoCodec>,
+        audio: Option<AudioCodec>,
+    },
+    Mkv {
+        video: Option<VideoCodec>,
+        audio: Option<AudioCodec>,
+    },
+    Unknown {
+        container: String,
+        video: Option<VideoCodec>,
+        audio: Option<AudioCodec>,
+    },
+}
+
+/// Classify `info` into a [`MediaProfile`] by matching its ffprobe
+/// `format_name` against the handful of containers `validate_for_merge`
+/// knows how to reason about.
+pub fn classify_media(info: &MediaInfoResult) -> MediaProfile {
+    let video = info
+        .streams
+        .iter()
+        .find(|s| s.codec_type == "video")
+        .map(|s| VideoCodec::from_name(&s.codec_name));
+    let audio = info
+        .streams
+        .iter()
+        .find(|s| s.codec_type == "audio")
+        .map(|s| AudioCodec::from_name(&s.codec_name));
+
+    if info.format_name.contains("webm") {
+        MediaProfile::WebM { video, audio }
+    } else if info.format_name.contains("mp4") {
+        MediaProfile::Mp4 { video, audio }
+    } else if info.format_name.contains("matroska") {
+        MediaProfile::Mkv { video, audio }
+    } else {
+        MediaProfile::Unknown {
+            container: info.format_name.clone(),
+            video,
+            audio,
+        }
+    }
+}
+
+/// Whether `target_container` (a plain extension-style name: `mp4`, `webm`,
+/// `mkv`, ...) can legally hold `video`/`audio` without a remux problem —
+/// e.g. opus-in-mp4 is fragile and vorbis isn't valid in mp4 at all, so both
+/// come back `false` there even though ffmpeg may not refuse to spawn.
+fn container_supports(target_container: &str, video: &VideoCodec, audio: &AudioCodec) -> (bool, bool) {
+    match target_container {
+        "mp4" | "m4v" | "mov" => (
+            matches!(video, VideoCodec::H264 | VideoCodec::H265 | VideoCodec::Av1),
+            matches!(audio, AudioCodec::Aac),
+        ),
+        "webm" => (
+            matches!(video, VideoCodec::Vp9 | VideoCodec::Av1),
+            matches!(audio, AudioCodec::Opus | AudioCodec::Vorbis),
+        ),
+        "mkv" | "matroska" => (true, true),
+        _ => (false, false),
+    }
+}
+
+fn default_video_codec_for(target_container: &str) -> &'static str {
+    match target_container {
+        "webm" => "libvpx-vp9",
+        _ => "libx264",
+    }
+}
+
+fn default_audio_codec_for(target_container: &str) -> &'static str {
+    match target_container {
+        "webm" => "libopus",
+        _ => "aac",
+    }
+}
+
+/// Optional caps `validate_for_merge` enforces before it will plan a merge
+/// at all — independent of container/codec compatibility, which is always
+/// checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MergeLimits {
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+    pub max_duration_secs: Option<f64>,
+    pub max_file_size_bytes: Option<u64>,
+    pub allowed_video_codecs: Option<Vec<String>>,
+    pub allowed_audio_codecs: Option<Vec<String>>,
+}
+
+/// Result of `validate_for_merge`: whether each stream can be stream-copied
+/// into `target_container` as-is, or which codec to transcode it to if not.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergePlan {
+    pub target_container: String,
+    pub copy_video: bool,
+    pub copy_audio: bool,
+    pub recommended_video_codec: Option<String>,
+    pub recommended_audio_codec: Option<String>,
+    pub notes: Vec<String>,
+}
+
+/// Inspect `video_path` and `audio_path` via `get_media_info`, map the first
+/// video/audio stream's codec name onto [`VideoCodec`]/[`AudioCodec`], and
+/// work out whether `merge_video_audio` can
+/// safely stream-copy them into `target_container` — rejecting early (with
+/// a clear reason) anything that violates `limits`, and otherwise returning
+/// a plan that tells the caller which stream(s), if any, need a transcode
+/// rather than a plain `-c copy`.
+pub async fn validate_for_merge(
+    video_path: &str,
+    audio_path: &str,
+    target_container: &str,
+    limits: Option<&MergeLimits>,
+) -> Result<MergePlan, String> {
+    let video_info = get_media_info(video_path).await?;
+    let audio_info = get_media_info(audio_path).await?;
+
+    let video_stream = video_info
+        .streams
+        .iter()
+        .find(|s| s.codec_type == "video")
+        .ok_or_else(|| format!("{} has no video stream", video_path))?;
+    let audio_stream = audio_info
+        .streams
+        .iter()
+        .find(|s| s.codec_type == "audio")
+        .ok_or_else(|| format!("{} has no audio stream", audio_path))?;
+
+    if let Some(limits) = limits {
+        if let (Some(max_w), Some(w)) = (limits.max_width, video_stream.width) {
+            if w > max_w {
+                return Err(format!("Video width {} exceeds the allowed maximum of {}", w, max_w));
+            }
+        }
+        if let (Some(max_h), Some(h)) = (limits.max_height, video_stream.height) {
+            if h > max_h {
+                return Err(format!("Video height {} exceeds the allowed maximum of {}", h, max_h));
+            }
+        }
+        if let (Some(max_dur), Some(dur)) = (limits.max_duration_secs, video_info.duration) {
+            if dur > max_dur {
+                return Err(format!(
+                    "Video duration {:.1}s exceeds the allowed maximum of {:.1}s",
+                    dur, max_dur
+                ));
+            }
+        }
+        if let Some(max_size) = limits.max_file_size_bytes {
+            if video_info.size > max_size {
+                return Err(format!(
+                    "Video file size {} bytes exceeds the allowed maximum of {} bytes",
+                    video_info.size, max_size
+                ));
+            }
+        }
+        if let Some(allowed) = &limits.allowed_video_codecs {
+            if !allowed.iter().any(|c| c.eq_ignore_ascii_case(&video_stream.codec_name)) {
+                return Err(format!("Video codec '{}' is not in the allowed list", video_stream.codec_name));
+            }
+        }
+        if let Some(allowed) = &limits.allowed_audio_codecs {
+            if !allowed.iter().any(|c| c.eq_ignore_ascii_case(&audio_stream.codec_name)) {
+                return Err(format!("Audio codec '{}' is not in the allowed list", audio_stream.codec_name));
+            }
+        }
+    }
+
+    let video_codec = VideoCodec::from_name(&video_stream.codec_name);
+    let audio_codec = AudioCodec::from_name(&audio_stream.codec_name);
+    let (video_compatible, audio_compatible) = container_supports(target_container, &video_codec, &audio_codec);
+
+    let mut notes = Vec::new();
+    let recommended_video_codec = if video_compatible {
+        None
+    } else {
+        notes.push(format!(
+            "{:?} is not supported in {} — video will be transcoded",
+            video_codec, target_container
+        ));
+        Some(default_video_codec_for(target_container).to_string())
+    };
+    let recommended_audio_codec = if audio_compatible {
+        None
+    } else {
+        notes.push(format!(
+            "{:?} is not supported in {} — audio will be transcoded",
+            audio_codec, target_container
+        ));
+        Some(default_audio_codec_for(target_container).to_string())
+    };
+
+    Ok(MergePlan {
+        target_container: target_container.to_string(),
+        copy_video: video_compatible,
+        copy_audio: audio_compatible,
+        recommended_video_codec,
+        recommended_audio_codec,
+        notes,
+    })
+}
+
+/// Get the path to the ffmpeg binary: prefer the one `binary_resolver` has
+/// already downloaded and cached, falling back to ffmpeg-sidecar's own
+/// resolution (PATH, then the app's resource directory) if resolution hasn't
+/// run yet or didn't find a matching release asset for this platform.
 pub fn get_ffmpeg_path() -> std::path::PathBuf {
-    // ffmpeg-sidecar will automatically find the binary
-    // For Tauri sidecar, it's in the app's resource directory
-    ffmpeg_sidecar::paths::ffmpeg_path()
+    crate::binary_resolver::resolved_path(crate::binary_resolver::Tool::Ffmpeg)
+        .unwrap_or_else(ffmpeg_sidecar::paths::ffmpeg_path)
 }
 
 // ============ MEDIA INFO ============
@@ -129,6 +433,115 @@ pub struct MediaInfoResult {
     pub size: u64,
     pub bit_rate: Option<u64>,
     pub streams: Vec<StreamInfo>,
+    /// Condensed discover-style summary of `streams`/`format_name`, handed
+    /// back alongside the raw ffprobe fields so the UI can pre-populate
+    /// sensible output settings (e.g. only offer audio extraction when
+    /// `has_audio` is true) without re-deriving it itself.
+    pub probe: MediaProbe,
+}
+
+/// Typed, UI-friendly summary of a probed input: the handful of fields the
+/// `ffmpeg_*` commands' preflight step actually reasons about, condensed out
+/// of `MediaInfoResult`'s raw per-stream ffprobe data in `get_media_info`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaProbe {
+    pub container: String,
+    pub duration: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub video_codec: Option<String>,
+    pub pixel_format: Option<String>,
+    pub audio_codec: Option<String>,
+    pub sample_rate: Option<String>,
+    pub has_audio: bool,
+}
+
+/// Why `preflight` rejected an input, in a shape the frontend can switch on
+/// instead of pattern-matching a free-form message: `reason` is a stable
+/// machine-readable tag (`"unsupported_codec"`, `"resolution_too_large"`,
+/// ...) and `detail` carries the offending value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightRejection {
+    pub reason: String,
+    pub detail: String,
+}
+
+impl std::fmt::Display for PreflightRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).unwrap_or_else(|_| self.reason.clone())
+        )
+    }
+}
+
+/// Discover-style preflight: probe `input_path` via `get_media_info` and
+/// reject early (before a caller spawns the real ffmpeg job) if it violates
+/// `limits`. Mirrors `validate_for_merge`'s early-rejection approach, but runs
+/// ahead of every job-running `ffmpeg_*` command rather than just the merge
+/// ones.
+pub async fn preflight(input_path: &str, limits: &crate::config::FfmpegLimits) -> Result<MediaProbe, PreflightRejection> {
+    let info = get_media_info(input_path).await.map_err(|e| PreflightRejection {
+        reason: "probe_failed".to_string(),
+        detail: e,
+    })?;
+    let probe = info.probe;
+
+    if let (Some(max_w), Some(w)) = (limits.max_width, probe.width) {
+        if w > max_w {
+            return Err(PreflightRejection {
+                reason: "resolution_too_large".to_string(),
+                detail: format!("width {} exceeds the allowed maximum of {}", w, max_w),
+            });
+        }
+    }
+    if let (Some(max_h), Some(h)) = (limits.max_height, probe.height) {
+        if h > max_h {
+            return Err(PreflightRejection {
+                reason: "resolution_too_large".to_string(),
+                detail: format!("height {} exceeds the allowed maximum of {}", h, max_h),
+            });
+        }
+    }
+    if let (Some(max_dur), Some(dur)) = (limits.max_duration_secs, probe.duration) {
+        if dur > max_dur {
+            return Err(PreflightRejection {
+                reason: "duration_too_long".to_string(),
+                detail: format!("{:.1}s exceeds the allowed maximum of {:.1}s", dur, max_dur),
+            });
+        }
+    }
+    if let Some(allowed) = &limits.allowed_video_codecs {
+        if let Some(codec) = &probe.video_codec {
+            if !allowed.iter().any(|c| c.eq_ignore_ascii_case(codec)) {
+                return Err(PreflightRejection {
+                    reason: "unsupported_codec".to_string(),
+                    detail: codec.clone(),
+                });
+            }
+        }
+    }
+    if let Some(allowed) = &limits.allowed_audio_codecs {
+        if let Some(codec) = &probe.audio_codec {
+            if !allowed.iter().any(|c| c.eq_ignore_ascii_case(codec)) {
+                return Err(PreflightRejection {
+                    reason: "unsupported_codec".to_string(),
+                    detail: codec.clone(),
+                });
+            }
+        }
+    }
+    if let Some(allowed) = &limits.allowed_containers {
+        if !allowed.iter().any(|c| probe.container.contains(c.as_str())) {
+            return Err(PreflightRejection {
+                reason: "unsupported_container".to_string(),
+                detail: probe.container.clone(),
+            });
+        }
+    }
+
+    Ok(probe)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -139,6 +552,7 @@ pub struct StreamInfo {
     pub codec_long_name: Option<String>,
     pub width: Option<u32>,
     pub height: Option<u32>,
+    pub pix_fmt: Option<String>,
     pub sample_rate: Option<String>,
     pub channels: Option<u32>,
     pub bit_rate: Option<String>,
@@ -188,6 +602,7 @@ pub async fn get_media_info(input_path: &str) -> Result<MediaInfoResult, String>
                     codec_long_name: s.get("codec_long_name").and_then(|v| v.as_str()).map(|s| s.to_string()),
                     width: s.get("width").and_then(|v| v.as_u64()).map(|v| v as u32),
                     height: s.get("height").and_then(|v| v.as_u64()).map(|v| v as u32),
+                    pix_fmt: s.get("pix_fmt").and_then(|v| v.as_str()).map(|s| s.to_string()),
                     sample_rate: s.get("sample_rate").and_then(|v| v.as_str()).map(|s| s.to_string()),
                     channels: s.get("channels").and_then(|v| v.as_u64()).map(|v| v as u32),
                     bit_rate: s.get("bit_rate").and_then(|v| v.as_str()).map(|s| s.to_string()),
@@ -196,14 +611,32 @@ pub async fn get_media_info(input_path: &str) -> Result<MediaInfoResult, String>
             }
         }
 
+        let video_stream = streams.iter().find(|s: &&StreamInfo| s.codec_type == "video").cloned();
+        let audio_stream = streams.iter().find(|s: &&StreamInfo| s.codec_type == "audio").cloned();
+        let format_name = format.get("format_name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let duration = format.get("duration").and_then(|v| v.as_str()).and_then(|s| s.parse().ok());
+
+        let probe = MediaProbe {
+            container: format_name.clone(),
+            duration,
+            width: video_stream.as_ref().and_then(|s| s.width),
+            height: video_stream.as_ref().and_then(|s| s.height),
+            video_codec: video_stream.as_ref().map(|s| s.codec_name.clone()),
+            pixel_format: video_stream.as_ref().and_then(|s| s.pix_fmt.clone()),
+            audio_codec: audio_stream.as_ref().map(|s| s.codec_name.clone()),
+            sample_rate: audio_stream.as_ref().and_then(|s| s.sample_rate.clone()),
+            has_audio: audio_stream.is_some(),
+        };
+
         Ok(MediaInfoResult {
             filename: format.get("filename").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-            format_name: format.get("format_name").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            format_name,
             format_long_name: format.get("format_long_name").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-            duration: format.get("duration").and_then(|v| v.as_str()).and_then(|s| s.parse().ok()),
+            duration,
             size: format.get("size").and_then(|v| v.as_str()).and_then(|s| s.parse().ok()).unwrap_or(0),
             bit_rate: format.get("bit_rate").and_then(|v| v.as_str()).and_then(|s| s.parse().ok()),
             streams,
+            probe,
         })
     })
     .await
@@ -318,16 +751,37 @@ pub fn compress_video_sync(
     Ok(())
 }
 
-// ============ VIDEO TRIM ============
+// ============ VIDEO COMPRESS (TARGET VMAF) ============
 
-/// Trim video to specified time range
-pub fn trim_video_sync(
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetVmafResult {
+    pub crf: u8,
+    pub vmaf: f32,
+}
+
+const TARGET_VMAF_CRF_MIN: u8 = 18;
+const TARGET_VMAF_CRF_MAX: u8 = 40;
+const TARGET_VMAF_TOLERANCE: f32 = 0.5;
+const TARGET_VMAF_MAX_PROBES: u32 = 8;
+const TARGET_VMAF_SAMPLE_SECS: f64 = 2.0;
+
+/// Compress `input_path` to a target VMAF score instead of a fixed CRF, the
+/// way Av1an's target-quality mode works: probe three 2-second sample
+/// windows (at 25/50/75% of the duration) at candidate CRFs, measure VMAF
+/// against a lossless re-encode of each window with `libvmaf`, and bisect
+/// the valid CRF range (18-40) until the averaged score lands within
+/// `TARGET_VMAF_TOLERANCE` of `target_vmaf` or the bracket narrows to a
+/// single CRF step. After two endpoint probes, each next CRF is predicted by
+/// linearly interpolating between the nearest bracketing (crf, score)
+/// pairs; VMAF is assumed monotonically decreasing in CRF, so the bracket
+/// stays valid throughout. The final encode (at the chosen CRF, over the
+/// whole input) reuses `compress_video_sync`.
+pub fn compress_video_target_vmaf_sync(
     input_path: &str,
     output_path: &str,
-    start_time: &str, // Format: "HH:MM:SS" or "SS"
-    end_time: &str,
+    target_vmaf: f32,
     progress_callback: impl Fn(f32) + Send + 'static,
-) -> Result<(), String> {
+) -> Result<TargetVmafResult, String> {
     if !Path::new(input_path).exists() {
         return Err(format!("Input file not found: {}", input_path));
     }
@@ -337,49 +791,266 @@ pub fn trim_video_sync(
             .map_err(|e| format!("Failed to create output directory: {}", e))?;
     }
 
-    let mut cmd = FfmpegCommand::new();
-    cmd.args(["-y"])
-        .args(["-ss", start_time])
-        .args(["-to", end_time])
-        .input(input_path)
-        .args(["-c", "copy"]) // Stream copy for fast trimming
-        .output(output_path);
+    let duration = probe_duration_secs(input_path)?;
+    let sample_starts: Vec<f64> = [0.25, 0.5, 0.75].iter().map(|frac| duration * frac).collect();
 
-    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
-    let mut error_msg: Option<String> = None;
+    let probe_dir = std::env::temp_dir().join(format!("vget-vmaf-probe-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&probe_dir)
+        .map_err(|e| format!("Failed to create probe directory: {}", e))?;
 
-    for event in child.iter().expect("Failed to iterate ffmpeg events") {
-        match event {
-            FfmpegEvent::Progress(progress) => {
-                if let Some(secs) = parse_time_to_secs(&progress.time) {
-                    progress_callback(secs);
-                }
-            }
-            FfmpegEvent::Log(LogLevel::Error, msg) => {
-                eprintln!("[ffmpeg error] {}", msg);
-                error_msg = Some(msg);
-            }
-            FfmpegEvent::Done => break,
-            _ => {}
+    let search_result = search_target_vmaf_crf(input_path, &sample_starts, target_vmaf, &probe_dir);
+    let _ = std::fs::remove_dir_all(&probe_dir);
+    let (crf, vmaf) = search_result?;
+
+    compress_video_sync(input_path, output_path, crf, progress_callback)?;
+
+    Ok(TargetVmafResult { crf, vmaf })
+}
+
+/// Bisect `TARGET_VMAF_CRF_MIN..=TARGET_VMAF_CRF_MAX` toward `target_vmaf`,
+/// returning the best `(crf, vmaf)` pair found.
+fn search_target_vmaf_crf(
+    input_path: &str,
+    sample_starts: &[f64],
+    target_vmaf: f32,
+    probe_dir: &Path,
+) -> Result<(u8, f32), String> {
+    let mut low = TARGET_VMAF_CRF_MIN;
+    let mut high = TARGET_VMAF_CRF_MAX;
+
+    let score_low = measure_vmaf_at_crf(input_path, sample_starts, low, probe_dir)?;
+    let score_high = measure_vmaf_at_crf(input_path, sample_starts, high, probe_dir)?;
+    let mut measured = vec![(low, score_low), (high, score_high)];
+
+    let closest = |measured: &[(u8, f32)]| {
+        *measured
+            .iter()
+            .min_by(|a, b| {
+                (a.1 - target_vmaf)
+                    .abs()
+                    .partial_cmp(&(b.1 - target_vmaf).abs())
+                    .unwrap()
+            })
+            .unwrap()
+    };
+    let mut best = closest(&measured);
+
+    for _ in 0..TARGET_VMAF_MAX_PROBES {
+        if (best.1 - target_vmaf).abs() <= TARGET_VMAF_TOLERANCE || high <= low + 1 {
+            break;
+        }
+
+        let score_low = measured.iter().find(|(c, _)| *c == low).unwrap().1;
+        let score_high = measured.iter().find(|(c, _)| *c == high).unwrap().1;
+
+        let span = score_low - score_high;
+        let predicted = if span.abs() < f32::EPSILON {
+            (low as f32 + high as f32) / 2.0
+        } else {
+            low as f32 + (high - low) as f32 * (score_low - target_vmaf) / span
+        };
+        let candidate = (predicted.round() as i32).clamp(low as i32 + 1, high as i32 - 1) as u8;
+
+        let score = measure_vmaf_at_crf(input_path, sample_starts, candidate, probe_dir)?;
+        measured.push((candidate, score));
+
+        if score >= target_vmaf {
+            low = candidate;
+        } else {
+            high = candidate;
+        }
+
+        if (score - target_vmaf).abs() < (best.1 - target_vmaf).abs() {
+            best = (candidate, score);
         }
     }
 
-    if !Path::new(output_path).exists() {
-        return Err(error_msg.unwrap_or_else(|| "FFmpeg failed to create output file".to_string()));
+    Ok(best)
+}
+
+/// Encode each sample window at `crf` (reusing a lossless reference
+/// re-encode across CRFs) and return the averaged VMAF score across
+/// windows.
+fn measure_vmaf_at_crf(
+    input_path: &str,
+    sample_starts: &[f64],
+    crf: u8,
+    probe_dir: &Path,
+) -> Result<f32, String> {
+    let crf_str = crf.to_string();
+    let sample_secs = TARGET_VMAF_SAMPLE_SECS.to_string();
+    let mut scores = Vec::with_capacity(sample_starts.len());
+
+    for (i, start) in sample_starts.iter().enumerate() {
+        let start_str = format!("{:.3}", start);
+        let reference_path = probe_dir.join(format!("ref_{}.mp4", i));
+        let encoded_path = probe_dir.join(format!("enc_{}_{}.mp4", i, crf));
+        let vmaf_log_path = probe_dir.join(format!("vmaf_{}_{}.json", i, crf));
+
+        if !reference_path.exists() {
+            let mut cmd = FfmpegCommand::new();
+            cmd.args(["-y"])
+                .args(["-ss", &start_str])
+                .args(["-t", &sample_secs])
+                .input(input_path)
+                .args(["-c:v", "libx264", "-crf", "0", "-preset", "ultrafast", "-an"])
+                .output(reference_path.to_string_lossy().as_ref());
+            run_ffmpeg_to_completion(&mut cmd)?;
+        }
+
+        let mut cmd = FfmpegCommand::new();
+        cmd.args(["-y"])
+            .args(["-ss", &start_str])
+            .args(["-t", &sample_secs])
+            .input(input_path)
+            .args(["-c:v", "libx264", "-crf", &crf_str, "-preset", "fast", "-an"])
+            .output(encoded_path.to_string_lossy().as_ref());
+        run_ffmpeg_to_completion(&mut cmd)?;
+
+        let filter = format!("libvmaf=log_fmt=json:log_path={}", vmaf_log_path.to_string_lossy());
+        let mut cmd = FfmpegCommand::new();
+        cmd.args(["-y"])
+            .input(encoded_path.to_string_lossy().as_ref())
+            .input(reference_path.to_string_lossy().as_ref())
+            .args(["-lavfi", &filter])
+            .args(["-f", "null"])
+            .output("-");
+        run_ffmpeg_to_completion(&mut cmd)?;
+
+        scores.push(parse_vmaf_log(&vmaf_log_path)?);
     }
 
-    Ok(())
+    Ok(scores.iter().sum::<f32>() / scores.len() as f32)
 }
 
-// ============ EXTRACT AUDIO ============
+fn parse_vmaf_log(path: &Path) -> Result<f32, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read VMAF log {}: {}", path.display(), e))?;
+    let json: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse VMAF log: {}", e))?;
+
+    json.get("pooled_metrics")
+        .and_then(|m| m.get("vmaf"))
+        .and_then(|v| v.get("mean"))
+        .and_then(|v| v.as_f64())
+        .map(|v| v as f32)
+        .ok_or_else(|| "VMAF log missing pooled mean score".to_string())
+}
 
-/// Extract audio from video file
-pub fn extract_audio_sync(
+fn probe_duration_secs(input_path: &str) -> Result<f64, String> {
+    let ffprobe_path = ffmpeg_sidecar::ffprobe::ffprobe_path();
+
+    let output = Command::new(ffprobe_path)
+        .args(["-v", "quiet", "-print_format", "json", "-show_format", input_path])
+        .output()
+        .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err("ffprobe failed to analyze file".to_string());
+    }
+
+    let json_str = String::from_utf8_lossy(&output.stdout);
+    let probe: serde_json::Value =
+        serde_json::from_str(&json_str).map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+
+    probe
+        .get("format")
+        .and_then(|f| f.get("duration"))
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<f64>().ok())
+        .ok_or_else(|| "Could not determine input duration".to_string())
+}
+
+fn probe_fps(input_path: &str) -> Option<f32> {
+    let ffprobe_path = ffmpeg_sidecar::ffprobe::ffprobe_path();
+
+    let output = Command::new(ffprobe_path)
+        .args([
+            "-v", "quiet",
+            "-select_streams", "v:0",
+            "-show_entries", "stream=r_frame_rate",
+            "-print_format", "json",
+            input_path,
+        ])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let json_str = String::from_utf8_lossy(&output.stdout);
+    let probe: serde_json::Value = serde_json::from_str(&json_str).ok()?;
+    let rate = probe.get("streams")?.get(0)?.get("r_frame_rate")?.as_str()?;
+
+    let mut parts = rate.split('/');
+    let num: f32 = parts.next()?.parse().ok()?;
+    let den: f32 = parts.next().unwrap_or("1").parse().ok()?;
+
+    if den == 0.0 {
+        None
+    } else {
+        Some(num / den)
+    }
+}
+
+// ============ CHUNKED ENCODE ============
+
+/// Codec/quality settings applied to every chunk in `encode_chunked`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkEncodeSettings {
+    pub video_codec: String,
+    pub crf: u8,
+    pub preset: String,
+    pub audio_codec: String,
+}
+
+impl Default for ChunkEncodeSettings {
+    fn default() -> Self {
+        Self {
+            video_codec: "libx264".to_string(),
+            crf: 23,
+            preset: "medium".to_string(),
+            audio_codec: "aac".to_string(),
+        }
+    }
+}
+
+/// Start/end/encode-duration of one chunk from `encode_chunked`, returned so
+/// callers can inspect per-worker timing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkTiming {
+    pub index: usize,
+    pub start: f64,
+    pub end: f64,
+    pub encode_secs: f64,
+}
+
+// Chunk boundaries are kept within this frame-count range so scene
+// detection neither creates thousands of tiny segments nor lets a single
+// static scene balloon into one giant (effectively unparallelized) chunk.
+const CHUNK_MIN_FRAMES: u32 = 10;
+const CHUNK_MAX_FRAMES: u32 = 250;
+const SCENE_CHANGE_THRESHOLD: f32 = 0.3;
+
+/// Split `input_path` into independent segments at detected scene-change
+/// boundaries, encode them concurrently across `workers` threads (inspired
+/// by Av1an's chunked-encode pipeline), and concatenate losslessly into
+/// `output_path`. Each chunk is encoded with `-reset_timestamps 1` so its
+/// timestamps start at zero and stream-copy concat produces seamless
+/// output. Cut points are the detected scene changes plus the extra splits
+/// `group_cuts_into_chunks` forces to cap chunk length, so they need not
+/// fall on source keyframes; each chunk is re-encoded from its own `-ss`
+/// input seek, so its output starts on a keyframe of its own.
+/// `progress_callback` receives the fraction of total frames encoded so far,
+/// summed across all workers.
+pub fn encode_chunked(
     input_path: &str,
     output_path: &str,
-    format: &str, // mp3, aac, flac, wav
-    progress_callback: impl Fn(f32) + Send + 'static,
-) -> Result<(), String> {
+    settings: ChunkEncodeSettings,
+    workers: usize,
+    progress_callback: impl Fn(f32) + Send + Sync + 'static,
+) -> Result<Vec<ChunkTiming>, String> {
     if !Path::new(input_path).exists() {
         return Err(format!("Input file not found: {}", input_path));
     }
@@ -389,61 +1060,521 @@ pub fn extract_audio_sync(
             .map_err(|e| format!("Failed to create output directory: {}", e))?;
     }
 
-    let mut cmd = FfmpegCommand::new();
-    cmd.args(["-y"])
-        .input(input_path)
-        .args(["-vn"]); // No video
+    let duration = probe_duration_secs(input_path)?;
+    let fps = probe_fps(input_path).unwrap_or(30.0);
+
+    let cut_points = detect_scene_cuts(input_path)?;
+    let boundaries = group_cuts_into_chunks(&cut_points, duration, fps);
+
+    let workers = workers.max(1).min(boundaries.len().max(1));
+    let work_dir = std::env::temp_dir().join(format!("vget-chunked-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&work_dir)
+        .map_err(|e| format!("Failed to create work directory: {}", e))?;
+
+    let total_frames = (duration * fps as f64).max(1.0);
+    let completed_frames = Arc::new(AtomicU64::new(0));
+    let progress_callback = Arc::new(progress_callback);
+    let settings = Arc::new(settings);
+    let queue: Arc<Mutex<VecDeque<(usize, (f64, f64))>>> =
+        Arc::new(Mutex::new(boundaries.into_iter().enumerate().collect()));
+    let timings: Arc<Mutex<Vec<ChunkTiming>>> = Arc::new(Mutex::new(Vec::new()));
+    let error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            let queue = Arc::clone(&queue);
+            let timings = Arc::clone(&timings);
+            let error = Arc::clone(&error);
+            let completed_frames = Arc::clone(&completed_frames);
+            let progress_callback = Arc::clone(&progress_callback);
+            let settings = Arc::clone(&settings);
+            let work_dir = work_dir.clone();
+
+            scope.spawn(move || loop {
+                if error.lock().unwrap().is_some() {
+                    return;
+                }
 
-    // Set codec based on format
-    match format {
-        "mp3" => {
-            cmd.args(["-c:a", "libmp3lame"]);
-            cmd.args(["-b:a", "192k"]);
-        }
-        "aac" => {
-            cmd.args(["-c:a", "aac"]);
-            cmd.args(["-b:a", "192k"]);
-        }
-        "flac" => {
-            cmd.args(["-c:a", "flac"]);
-        }
-        "wav" => {
-            cmd.args(["-c:a", "pcm_s16le"]);
-        }
-        _ => {
-            cmd.args(["-c:a", "copy"]); // Try to copy
+                let Some((index, (start, end))) = queue.lock().unwrap().pop_front() else {
+                    return;
+                };
+
+                let chunk_path = work_dir.join(format!("chunk_{:05}.mp4", index));
+                let started = std::time::Instant::now();
+
+                match encode_chunk(input_path, &chunk_path, start, end, &settings) {
+                    Ok(()) => {
+                        let frames_in_chunk = ((end - start) * fps as f64).max(1.0) as u64;
+                        let done = completed_frames.fetch_add(frames_in_chunk, Ordering::SeqCst) + frames_in_chunk;
+                        progress_callback((done as f64 / total_frames) as f32);
+
+                        timings.lock().unwrap().push(ChunkTiming {
+                            index,
+                            start,
+                            end,
+                            encode_secs: started.elapsed().as_secs_f64(),
+                        });
+                    }
+                    Err(e) => {
+                        *error.lock().unwrap() = Some(e);
+                        return;
+                    }
+                }
+            });
         }
+    });
+
+    if let Some(e) = error.lock().unwrap().take() {
+        let _ = std::fs::remove_dir_all(&work_dir);
+        return Err(e);
     }
 
-    cmd.output(output_path);
+    let mut timings = Arc::try_unwrap(timings)
+        .map_err(|_| "Internal error: chunk timings still shared after workers finished".to_string())?
+        .into_inner()
+        .map_err(|e| format!("Internal error: poisoned timings mutex: {}", e))?;
+    timings.sort_by_key(|t| t.index);
+
+    let concat_result = concat_chunks(&work_dir, timings.len(), output_path);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    concat_result?;
+
+    Ok(timings)
+}
+
+/// Run a scene-detection pass (`select='gt(scene,N)',showinfo`) and collect
+/// the presentation timestamp of every selected (i.e. scene-change) frame.
+fn detect_scene_cuts(input_path: &str) -> Result<Vec<f64>, String> {
+    let filter = format!("select='gt(scene,{})',showinfo", SCENE_CHANGE_THRESHOLD);
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"])
+        .input(input_path)
+        .args(["-vf", &filter])
+        .args(["-an"])
+        .args(["-f", "null"])
+        .output("-");
 
     let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
-    let mut error_msg: Option<String> = None;
+    let mut cuts = Vec::new();
 
     for event in child.iter().expect("Failed to iterate ffmpeg events") {
-        match event {
-            FfmpegEvent::Progress(progress) => {
-                if let Some(secs) = parse_time_to_secs(&progress.time) {
-                    progress_callback(secs);
+        if let FfmpegEvent::Log(_, msg) = event {
+            if let Some(idx) = msg.find("pts_time:") {
+                let rest = &msg[idx + "pts_time:".len()..];
+                if let Some(time_str) = rest.split_whitespace().next() {
+                    if let Ok(t) = time_str.parse::<f64>() {
+                        cuts.push(t);
+                    }
                 }
             }
-            FfmpegEvent::Log(LogLevel::Error, msg) => {
-                eprintln!("[ffmpeg error] {}", msg);
-                error_msg = Some(msg);
-            }
-            FfmpegEvent::Done => break,
-            _ => {}
         }
     }
 
-    if !Path::new(output_path).exists() {
-        return Err(error_msg.unwrap_or_else(|| "FFmpeg failed to create output file".to_string()));
+    cuts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(cuts)
+}
+
+/// Turn raw scene-cut timestamps into `[start, end)` chunk boundaries
+/// covering `0..duration`, merging cuts closer than `CHUNK_MIN_FRAMES` to
+/// the previous boundary and forcing extra cuts so no chunk exceeds
+/// `CHUNK_MAX_FRAMES`.
+fn group_cuts_into_chunks(cut_points: &[f64], duration: f64, fps: f32) -> Vec<(f64, f64)> {
+    let min_len = CHUNK_MIN_FRAMES as f64 / fps as f64;
+    let max_len = CHUNK_MAX_FRAMES as f64 / fps as f64;
+
+    let mut boundaries = Vec::new();
+    let mut chunk_start = 0.0;
+
+    for &cut in cut_points {
+        if cut <= chunk_start || cut - chunk_start < min_len {
+            continue;
+        }
+
+        push_bounded_range(&mut boundaries, chunk_start, cut, max_len);
+        chunk_start = cut;
     }
 
-    Ok(())
+    if chunk_start < duration {
+        push_bounded_range(&mut boundaries, chunk_start, duration, max_len);
+    }
+
+    boundaries
 }
 
-// ============ EXTRACT FRAMES ============
+/// Push `[start, end)` onto `boundaries`, splitting it first into
+/// consecutive `max_len` pieces with a final, possibly shorter, remainder.
+fn push_bounded_range(boundaries: &mut Vec<(f64, f64)>, start: f64, end: f64, max_len: f64) {
+    let mut cursor = start;
+    while end - cursor > max_len {
+        let next = cursor + max_len;
+        boundaries.push((cursor, next));
+        cursor = next;
+    }
+    boundaries.push((cursor, end));
+}
+
+fn encode_chunk(
+    input_path: &str,
+    chunk_path: &Path,
+    start: f64,
+    end: f64,
+    settings: &ChunkEncodeSettings,
+) -> Result<(), String> {
+    let crf_str = settings.crf.to_string();
+    let start_str = format!("{:.3}", start);
+    let to_str = format!("{:.3}", end);
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"])
+        .args(["-ss", &start_str])
+        .args(["-to", &to_str])
+        .input(input_path)
+        .args(["-c:v", &settings.video_codec])
+        .args(["-crf", &crf_str])
+        .args(["-preset", &settings.preset])
+        .args(["-c:a", &settings.audio_codec])
+        .args(["-reset_timestamps", "1"])
+        .output(chunk_path.to_string_lossy().as_ref());
+
+    run_ffmpeg_to_completion(&mut cmd)?;
+
+    if !chunk_path.exists() {
+        return Err(format!("Chunk encode produced no output for [{}, {})", start, end));
+    }
+
+    Ok(())
+}
+
+/// Build a concat-demuxer list file for `chunk_00000.mp4..chunk_{count-1}.mp4`
+/// under `work_dir` and stream-copy them into `output_path`.
+fn concat_chunks(work_dir: &Path, count: usize, output_path: &str) -> Result<(), String> {
+    let list_path = work_dir.join("concat_list.txt");
+
+    let mut list = String::new();
+    for index in 0..count {
+        let chunk_path: PathBuf = work_dir.join(format!("chunk_{:05}.mp4", index));
+        list.push_str(&format!("file '{}'\n", chunk_path.to_string_lossy()));
+    }
+    std::fs::write(&list_path, list).map_err(|e| format!("Failed to write concat list: {}", e))?;
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"])
+        .args(["-f", "concat", "-safe", "0"])
+        .input(list_path.to_string_lossy().as_ref())
+        .args(["-c", "copy"])
+        .output(output_path);
+
+    run_ffmpeg_to_completion(&mut cmd)?;
+
+    if !Path::new(output_path).exists() {
+        return Err("FFmpeg failed to create concatenated output file".to_string());
+    }
+
+    Ok(())
+}
+
+// ============ VIDEO TRIM ============
+
+/// Trim video to specified time range
+pub fn trim_video_sync(
+    input_path: &str,
+    output_path: &str,
+    start_time: &str, // Format: "HH:MM:SS" or "SS"
+    end_time: &str,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<(), String> {
+    if !Path::new(input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"])
+        .args(["-ss", start_time])
+        .args(["-to", end_time])
+        .input(input_path)
+        .args(["-c", "copy"]) // Stream copy for fast trimming
+        .output(output_path);
+
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
+    let mut error_msg: Option<String> = None;
+
+    for event in child.iter().expect("Failed to iterate ffmpeg events") {
+        match event {
+            FfmpegEvent::Progress(progress) => {
+                if let Some(secs) = parse_time_to_secs(&progress.time) {
+                    progress_callback(secs);
+                }
+            }
+            FfmpegEvent::Log(LogLevel::Error, msg) => {
+                eprintln!("[ffmpeg error] {}", msg);
+                error_msg = Some(msg);
+            }
+            FfmpegEvent::Done => break,
+            _ => {}
+        }
+    }
+
+    if !Path::new(output_path).exists() {
+        return Err(error_msg.unwrap_or_else(|| "FFmpeg failed to create output file".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Re-speed `input_path` over one or more `(start, end, factor)` ranges
+/// (seconds, `factor` > 1.0 speeds up, < 1.0 slows down), leaving everything
+/// outside those ranges at normal speed. Builds a `-filter_complex` that
+/// splits the timeline at every range boundary, applies `setpts=PTS/factor`
+/// to each segment's video and a chained `atempo` (atempo only accepts
+/// 0.5–2.0 per instance) to its audio, then stitches the pieces back
+/// together with the `concat` filter. ffmpeg's own progress reporting
+/// already reflects the post-filter (i.e. re-sped) output timeline, so
+/// `progress_callback` doesn't need any extra rescaling here.
+pub fn respeed_video_sync(
+    input_path: &str,
+    output_path: &str,
+    ranges: Vec<(f64, f64, f32)>,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<(), String> {
+    if !Path::new(input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    let duration = probe_duration_secs(input_path)?;
+    let ranges = normalize_respeed_ranges(ranges, duration)?;
+    let segments = build_respeed_segments(&ranges, duration);
+
+    if segments.is_empty() {
+        return Err("No segments to encode".to_string());
+    }
+
+    let mut filters = Vec::new();
+    let mut concat_inputs = String::new();
+    for (i, &(start, end, factor)) in segments.iter().enumerate() {
+        filters.push(format!(
+            "[0:v]trim=start={start}:end={end},setpts=(PTS-STARTPTS)/{factor}[v{i}]"
+        ));
+
+        let atempo = atempo_chain(factor)
+            .iter()
+            .map(|f| format!("atempo={}", f))
+            .collect::<Vec<_>>()
+            .join(",");
+        filters.push(format!(
+            "[0:a]atrim=start={start}:end={end},asetpts=PTS-STARTPTS,{atempo}[a{i}]"
+        ));
+
+        concat_inputs.push_str(&format!("[v{i}][a{i}]"));
+    }
+    filters.push(format!(
+        "{concat_inputs}concat=n={}:v=1:a=1[outv][outa]",
+        segments.len()
+    ));
+    let filter_complex = filters.join(";");
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"])
+        .input(input_path)
+        .args(["-filter_complex", &filter_complex])
+        .args(["-map", "[outv]"])
+        .args(["-map", "[outa]"])
+        .output(output_path);
+
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
+    let mut error_msg: Option<String> = None;
+
+    for event in child.iter().expect("Failed to iterate ffmpeg events") {
+        match event {
+            FfmpegEvent::Progress(progress) => {
+                if let Some(secs) = parse_time_to_secs(&progress.time) {
+                    progress_callback(secs);
+                }
+            }
+            FfmpegEvent::Log(LogLevel::Error, msg) => {
+                eprintln!("[ffmpeg error] {}", msg);
+                error_msg = Some(msg);
+            }
+            FfmpegEvent::Done => break,
+            _ => {}
+        }
+    }
+
+    if !Path::new(output_path).exists() {
+        return Err(error_msg.unwrap_or_else(|| "FFmpeg failed to create output file".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Sort `ranges` by start time, clamp them into `[0, duration]`, reject
+/// invalid entries (non-positive factor, inverted/empty range, or two
+/// ranges that overlap with different factors — ambiguous speed at that
+/// point), and merge ranges that touch and share the same factor.
+fn normalize_respeed_ranges(mut ranges: Vec<(f64, f64, f32)>, duration: f64) -> Result<Vec<(f64, f64, f32)>, String> {
+    if ranges.is_empty() {
+        return Err("At least one speed range is required".to_string());
+    }
+
+    for &(start, end, factor) in &ranges {
+        if factor <= 0.0 {
+            return Err(format!("Speed factor must be positive, got {}", factor));
+        }
+        if end <= start {
+            return Err(format!("Range end ({}) must be after start ({})", end, start));
+        }
+    }
+
+    ranges.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let mut merged: Vec<(f64, f64, f32)> = Vec::with_capacity(ranges.len());
+    for (start, end, factor) in ranges {
+        let start = start.clamp(0.0, duration);
+        let end = end.clamp(0.0, duration);
+        if end <= start {
+            continue;
+        }
+
+        if let Some(last) = merged.last_mut() {
+            if start < last.1 - f64::EPSILON && factor != last.2 {
+                return Err(format!(
+                    "Overlapping ranges with different speed factors at {}s",
+                    start
+                ));
+            }
+            if start <= last.1 && factor == last.2 {
+                last.1 = last.1.max(end);
+                continue;
+            }
+        }
+        merged.push((start, end, factor));
+    }
+
+    Ok(merged)
+}
+
+/// Fill the gaps between (and around) `ranges` with normal-speed (factor
+/// 1.0) segments so the result covers `[0, duration]` contiguously.
+fn build_respeed_segments(ranges: &[(f64, f64, f32)], duration: f64) -> Vec<(f64, f64, f32)> {
+    let mut segments = Vec::with_capacity(ranges.len() * 2 + 1);
+    let mut cursor = 0.0;
+
+    for &(start, end, factor) in ranges {
+        if start > cursor {
+            segments.push((cursor, start, 1.0));
+        }
+        segments.push((start, end, factor));
+        cursor = end;
+    }
+
+    if cursor < duration {
+        segments.push((cursor, duration, 1.0));
+    }
+
+    segments
+}
+
+/// Decompose an arbitrary positive speed `factor` into a chain of per-stage
+/// factors each within atempo's supported 0.5–2.0 range.
+fn atempo_chain(mut factor: f32) -> Vec<f32> {
+    let mut chain = Vec::new();
+
+    while factor > 2.0 {
+        chain.push(2.0);
+        factor /= 2.0;
+    }
+    while factor < 0.5 {
+        chain.push(0.5);
+        factor /= 0.5;
+    }
+    chain.push(factor);
+
+    chain
+}
+
+// ============ EXTRACT AUDIO ============
+
+/// Extract audio from video file
+pub fn extract_audio_sync(
+    input_path: &str,
+    output_path: &str,
+    format: &str, // mp3, aac, flac, wav
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<(), String> {
+    if !Path::new(input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"])
+        .input(input_path)
+        .args(["-vn"]); // No video
+
+    // Set codec based on format
+    match format {
+        "mp3" => {
+            cmd.args(["-c:a", "libmp3lame"]);
+            cmd.args(["-b:a", "192k"]);
+        }
+        "aac" => {
+            cmd.args(["-c:a", "aac"]);
+            cmd.args(["-b:a", "192k"]);
+        }
+        "flac" => {
+            cmd.args(["-c:a", "flac"]);
+        }
+        "wav" => {
+            cmd.args(["-c:a", "pcm_s16le"]);
+        }
+        _ => {
+            cmd.args(["-c:a", "copy"]); // Try to copy
+        }
+    }
+
+    cmd.output(output_path);
+
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
+    let mut error_msg: Option<String> = None;
+
+    for event in child.iter().expect("Failed to iterate ffmpeg events") {
+        match event {
+            FfmpegEvent::Progress(progress) => {
+                if let Some(secs) = parse_time_to_secs(&progress.time) {
+                    progress_callback(secs);
+                }
+            }
+            FfmpegEvent::Log(LogLevel::Error, msg) => {
+                eprintln!("[ffmpeg error] {}", msg);
+                error_msg = Some(msg);
+            }
+            FfmpegEvent::Done => break,
+            _ => {}
+        }
+    }
+
+    if !Path::new(output_path).exists() {
+        return Err(error_msg.unwrap_or_else(|| "FFmpeg failed to create output file".to_string()));
+    }
+
+    Ok(())
+}
+
+// ============ EXTRACT FRAMES ============
 
 /// Extract frames from video as images
 pub fn extract_frames_sync(
@@ -592,3 +1723,430 @@ pub fn convert_audio_sync(
 
     Ok(())
 }
+
+// ============ SEGMENTED OUTPUT (HLS / DASH) ============
+
+/// Result of packaging a file into adaptive-streaming segments: the
+/// manifest ffmpeg produced plus every segment file it wrote alongside it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentedOutput {
+    pub manifest_path: String,
+    pub segment_files: Vec<String>,
+}
+
+/// Package `input_path` into an HLS VOD playlist: a `seconds_per_segment`-second
+/// `.ts` segment chain plus an `out.m3u8` playlist, written under `output_dir`.
+/// Defaults to stream copy; pass `reencode_for_seeking` to force a keyframe at
+/// every segment boundary first, which a player needs to seek correctly.
+pub fn segment_hls_sync(
+    input_path: &str,
+    output_dir: &str,
+    seconds_per_segment: u32,
+    reencode_for_seeking: bool,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<SegmentedOutput, String> {
+    if !Path::new(input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let manifest_path = format!("{}/out.m3u8", output_dir);
+    let segment_pattern = format!("{}/seg_%04d.ts", output_dir);
+    let hls_time = seconds_per_segment.to_string();
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"]).input(input_path);
+    apply_segment_codec_args(&mut cmd, input_path, reencode_for_seeking, seconds_per_segment);
+    cmd.args(["-f", "hls"])
+        .args(["-hls_time", &hls_time])
+        .args(["-hls_playlist_type", "vod"])
+        .args(["-hls_segment_filename", &segment_pattern])
+        .output(&manifest_path);
+
+    run_segmenter(&mut cmd, &manifest_path, output_dir, "ts", progress_callback)
+}
+
+/// Package `input_path` into a DASH manifest (`out.mpd`) plus init/media
+/// segments, the DASH counterpart to `segment_hls_sync` with the same
+/// stream-copy default and optional keyframe-aligned re-encode.
+pub fn segment_dash_sync(
+    input_path: &str,
+    output_dir: &str,
+    seconds_per_segment: u32,
+    reencode_for_seeking: bool,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<SegmentedOutput, String> {
+    if !Path::new(input_path).exists() {
+        return Err(format!("Input file not found: {}", input_path));
+    }
+
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let manifest_path = format!("{}/out.mpd", output_dir);
+    let seg_duration = seconds_per_segment.to_string();
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"]).input(input_path);
+    apply_segment_codec_args(&mut cmd, input_path, reencode_for_seeking, seconds_per_segment);
+    cmd.args(["-f", "dash"]).args(["-seg_duration", &seg_duration]).output(&manifest_path);
+
+    run_segmenter(&mut cmd, &manifest_path, output_dir, "m4s", progress_callback)
+}
+
+/// Append either `-c copy` or a keyframe-aligned re-encode (`-g`/`-force_key_frames`
+/// tied to the detected input fps) to `cmd`, shared by both segmenters above.
+fn apply_segment_codec_args(
+    cmd: &mut FfmpegCommand,
+    input_path: &str,
+    reencode_for_seeking: bool,
+    seconds_per_segment: u32,
+) {
+    if !reencode_for_seeking {
+        cmd.args(["-c", "copy"]);
+        return;
+    }
+
+    let fps = probe_fps(input_path).unwrap_or(30.0);
+    let gop = ((fps * seconds_per_segment as f32).round() as u32).max(1).to_string();
+    let force_key_frames = format!("expr:gte(t,n_forced*{})", seconds_per_segment);
+
+    cmd.args(["-c:v", "libx264"])
+        .args(["-g", &gop])
+        .args(["-force_key_frames", &force_key_frames])
+        .args(["-c:a", "aac"]);
+}
+
+/// Spawn `cmd`, report progress, and on success scan `output_dir` for every
+/// generated `segment_ext` file (reusing `extract_frames_sync`'s directory-scan
+/// pattern) alongside the manifest ffmpeg wrote to `manifest_path`.
+fn run_segmenter(
+    cmd: &mut FfmpegCommand,
+    manifest_path: &str,
+    output_dir: &str,
+    segment_ext: &str,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<SegmentedOutput, String> {
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
+    let mut error_msg: Option<String> = None;
+
+    for event in child.iter().expect("Failed to iterate ffmpeg events") {
+        match event {
+            FfmpegEvent::Progress(progress) => {
+                if let Some(secs) = parse_time_to_secs(&progress.time) {
+                    progress_callback(secs);
+                }
+            }
+            FfmpegEvent::Log(LogLevel::Error, msg) => {
+                eprintln!("[ffmpeg error] {}", msg);
+                error_msg = Some(msg);
+            }
+            FfmpegEvent::Done => break,
+            _ => {}
+        }
+    }
+
+    if !Path::new(manifest_path).exists() {
+        return Err(error_msg.unwrap_or_else(|| "FFmpeg failed to create manifest file".to_string()));
+    }
+
+    let mut segment_files = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(output_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|e| e == segment_ext).unwrap_or(false) {
+                segment_files.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+    segment_files.sort();
+
+    Ok(SegmentedOutput {
+        manifest_path: manifest_path.to_string(),
+        segment_files,
+    })
+}
+
+// ============ TIMELINE ASSEMBLY ============
+
+/// One clip in an `assemble_timeline` timeline: a source file plus optional
+/// in/out trim points (seconds). `None` trims mean "from the very start" /
+/// "to the very end".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipSpec {
+    pub path: String,
+    pub trim_start: Option<f64>,
+    pub trim_end: Option<f64>,
+}
+
+/// Transition played between consecutive clips in `assemble_timeline`.
+/// `Fade` maps onto ffmpeg's built-in `fadeblack`/`fadewhite` xfade
+/// presets for those two colors and falls back to a plain crossfade
+/// (`fade`) for anything else, since xfade only ships solid-color fades
+/// through black or white. `Xfade` passes `kind` straight through as the
+/// `xfade` filter's `transition` name (e.g. `wipeleft`, `dissolve`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Transition {
+    None,
+    Fade { color: String, dur: f64 },
+    Xfade { kind: String, dur: f64 },
+}
+
+/// Stitch `clips` into one `output_path`, in order, optionally crossfading
+/// between each pair. With `Transition::None` this is a fast stream-copy
+/// concat; any other transition re-encodes through a `-filter_complex`
+/// xfade/acrossfade chain, since blending frames requires decoding them.
+pub async fn assemble_timeline(
+    clips: Vec<ClipSpec>,
+    output_path: &str,
+    transition: Transition,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<(), String> {
+    if clips.is_empty() {
+        return Err("At least one clip is required".to_string());
+    }
+    for clip in &clips {
+        if !Path::new(&clip.path).exists() {
+            return Err(format!("Clip not found: {}", clip.path));
+        }
+    }
+    if let Some(parent) = Path::new(output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    if matches!(transition, Transition::None) {
+        return assemble_timeline_concat(clips, output_path, progress_callback).await;
+    }
+
+    // The xfade/acrossfade graph needs each clip's trimmed duration up front
+    // to compute cumulative offsets, so probe them all before building the
+    // filter graph.
+    let mut durations = Vec::with_capacity(clips.len());
+    for clip in &clips {
+        let info = get_media_info(&clip.path).await?;
+        let full = info
+            .duration
+            .ok_or_else(|| format!("Could not determine duration for {}", clip.path))?;
+        let start = clip.trim_start.unwrap_or(0.0);
+        let end = clip.trim_end.unwrap_or(full);
+        durations.push((end - start).max(0.0));
+    }
+
+    let output_path = output_path.to_string();
+    tokio::task::spawn_blocking(move || {
+        assemble_timeline_xfade(&clips, &durations, &transition, &output_path, progress_callback)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Fast path for `Transition::None`: write an ffmpeg concat-demuxer list
+/// (using its `inpoint`/`outpoint` directives for trimming) and stream-copy
+/// it straight through.
+async fn assemble_timeline_concat(
+    clips: Vec<ClipSpec>,
+    output_path: &str,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<(), String> {
+    let output_path = output_path.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let list_path = format!("{}.concat.txt", output_path);
+        let mut list = String::new();
+        for clip in &clips {
+            list.push_str(&format!("file '{}'\n", clip.path.replace('\'', "'\\''")));
+            if let Some(start) = clip.trim_start {
+                list.push_str(&format!("inpoint {}\n", start));
+            }
+            if let Some(end) = clip.trim_end {
+                list.push_str(&format!("outpoint {}\n", end));
+            }
+        }
+        std::fs::write(&list_path, list).map_err(|e| format!("Failed to write concat list: {}", e))?;
+
+        let mut cmd = FfmpegCommand::new();
+        cmd.args(["-y", "-f", "concat", "-safe", "0"])
+            .input(&list_path)
+            .args(["-c", "copy"])
+            .output(&output_path);
+
+        let run_result = run_timeline_ffmpeg(&mut cmd, &progress_callback);
+        let _ = std::fs::remove_file(&list_path);
+        run_result?;
+
+        if !Path::new(&output_path).exists() {
+            return Err("FFmpeg failed to create output file".to_string());
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Transition path: trim every clip's video/audio streams down to its
+/// window, then chain `xfade`/`acrossfade` between consecutive clips with
+/// the offset of each transition set to the running length of the merged
+/// timeline so far minus the transition duration (so it lands exactly on
+/// the overlap between the two clips).
+fn assemble_timeline_xfade(
+    clips: &[ClipSpec],
+    durations: &[f64],
+    transition: &Transition,
+    output_path: &str,
+    progress_callback: impl Fn(f32) + Send + 'static,
+) -> Result<(), String> {
+    let (kind, dur) = match transition {
+        Transition::None => unreachable!("Transition::None is handled by assemble_timeline_concat"),
+        Transition::Fade { color, dur } => {
+            let kind = match color.to_lowercase().as_str() {
+                "white" => "fadewhite",
+                "black" => "fadeblack",
+                _ => "fade",
+            };
+            (kind.to_string(), *dur)
+        }
+        Transition::Xfade { kind, dur } => (kind.clone(), *dur),
+    };
+
+    let mut filters = Vec::new();
+    for (i, clip) in clips.iter().enumerate() {
+        let start = clip.trim_start.unwrap_or(0.0);
+        let end = start + durations[i];
+        filters.push(format!(
+            "[{i}:v]trim=start={start}:end={end},setpts=PTS-STARTPTS[v{i}]"
+        ));
+        filters.push(format!(
+            "[{i}:a]atrim=start={start}:end={end},asetpts=PTS-STARTPTS[a{i}]"
+        ));
+    }
+
+    let mut merged_len = durations[0];
+    let mut prev_v = "v0".to_string();
+    let mut prev_a = "a0".to_string();
+    for i in 1..clips.len() {
+        let offset = (merged_len - dur).max(0.0);
+        let vout = format!("vx{}", i);
+        let aout = format!("ax{}", i);
+        filters.push(format!(
+            "[{prev_v}][v{i}]xfade=transition={kind}:duration={dur}:offset={offset}[{vout}]"
+        ));
+        filters.push(format!("[{prev_a}][a{i}]acrossfade=d={dur}:c1=tri:c2=tri[{aout}]"));
+        prev_v = vout;
+        prev_a = aout;
+        merged_len += durations[i] - dur;
+    }
+
+    let filter_complex = filters.join(";");
+
+    let mut cmd = FfmpegCommand::new();
+    cmd.args(["-y"]);
+    for clip in clips {
+        cmd.input(&clip.path);
+    }
+    cmd.args(["-filter_complex", &filter_complex])
+        .args(["-map", &format!("[{}]", prev_v)])
+        .args(["-map", &format!("[{}]", prev_a)])
+        .output(output_path);
+
+    run_timeline_ffmpeg(&mut cmd, &progress_callback)?;
+
+    if !Path::new(output_path).exists() {
+        return Err("FFmpeg failed to create output file".to_string());
+    }
+    Ok(())
+}
+
+/// Spawn `cmd` and forward progress ticks, shared by both
+/// `assemble_timeline` paths.
+fn run_timeline_ffmpeg(
+    cmd: &mut FfmpegCommand,
+    progress_callback: &(impl Fn(f32) + Send + 'static),
+) -> Result<(), String> {
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg: {}", e))?;
+    let mut error_msg: Option<String> = None;
+
+    for event in child.iter().expect("Failed to iterate ffmpeg events") {
+        match event {
+            FfmpegEvent::Progress(progress) => {
+                if let Some(secs) = parse_time_to_secs(&progress.time) {
+                    progress_callback(secs);
+                }
+            }
+            FfmpegEvent::Log(LogLevel::Error, msg) => {
+                eprintln!("[ffmpeg error] {}", msg);
+                error_msg = Some(msg);
+            }
+            FfmpegEvent::Done => break,
+            _ => {}
+        }
+    }
+
+    match error_msg {
+        Some(msg) => Err(msg),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_respeed_ranges_rejects_empty_input() {
+        assert!(normalize_respeed_ranges(vec![], 10.0).is_err());
+    }
+
+    #[test]
+    fn normalize_respeed_ranges_rejects_zero_duration_range() {
+        assert!(normalize_respeed_ranges(vec![(1.0, 1.0, 2.0)], 10.0).is_err());
+    }
+
+    #[test]
+    fn normalize_respeed_ranges_rejects_non_positive_factor() {
+        assert!(normalize_respeed_ranges(vec![(0.0, 1.0, 0.0)], 10.0).is_err());
+    }
+
+    #[test]
+    fn normalize_respeed_ranges_rejects_conflicting_overlap() {
+        let ranges = vec![(0.0, 5.0, 2.0), (3.0, 6.0, 0.5)];
+        assert!(normalize_respeed_ranges(ranges, 10.0).is_err());
+    }
+
+    #[test]
+    fn normalize_respeed_ranges_merges_touching_same_factor_ranges() {
+        let ranges = vec![(0.0, 5.0, 2.0), (5.0, 8.0, 2.0)];
+        let merged = normalize_respeed_ranges(ranges, 10.0).unwrap();
+        assert_eq!(merged, vec![(0.0, 8.0, 2.0)]);
+    }
+
+    #[test]
+    fn normalize_respeed_ranges_clamps_to_duration() {
+        let ranges = vec![(-5.0, 20.0, 2.0)];
+        let merged = normalize_respeed_ranges(ranges, 10.0).unwrap();
+        assert_eq!(merged, vec![(0.0, 10.0, 2.0)]);
+    }
+
+    #[test]
+    fn atempo_chain_keeps_in_range_factor_as_single_stage() {
+        assert_eq!(atempo_chain(1.5), vec![1.5]);
+    }
+
+    #[test]
+    fn atempo_chain_splits_factors_above_two() {
+        let chain = atempo_chain(4.0);
+        assert!(chain.iter().all(|&f| (0.5..=2.0).contains(&f)));
+        let product: f32 = chain.iter().product();
+        assert!((product - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn atempo_chain_splits_factors_below_half() {
+        let chain = atempo_chain(0.1);
+        assert!(chain.iter().all(|&f| (0.5..=2.0).contains(&f)));
+        let product: f32 = chain.iter().product();
+        assert!((product - 0.1).abs() < 1e-4);
+    }
+}
@@ -1,21 +1,35 @@
+mod asciidoc;
 mod auth;
+mod binary_resolver;
 mod config;
 mod downloader;
 mod extractor;
 mod ffmpeg;
 mod md2pdf;
 mod pdf;
+#[cfg(feature = "discord-presence")]
+mod presence;
+mod preview;
+mod webdav;
 
 use auth::{
     bilibili_check_status, bilibili_logout, bilibili_qr_generate, bilibili_qr_poll,
     bilibili_save_cookie, xhs_check_status, xhs_logout, xhs_open_login_window,
 };
+use binary_resolver::{
+    get_binary_status as load_binary_status, resolve_binaries as do_resolve_binaries, BinaryStatus,
+};
 use config::{get_config as load_config, save_config as store_config, Config};
-use downloader::{DownloadJob, DownloadManager, DownloadStatus, SimpleDownloader};
-use extractor::{extract_media as do_extract, MediaInfo};
+use downloader::{DownloadJob, DownloadManager, DownloadStatus, HlsDownloader, SimpleDownloader};
+use extractor::{extract_media as do_extract, extract_media_ytdlp as do_extract_ytdlp, MediaInfo, YtDlpOptions, YtDlpResult};
 use ffmpeg::MediaInfoResult;
+use preview::PreviewRegistry;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tauri::{Emitter, State};
+use tokio::sync::Semaphore;
 
 // ============ CONFIG COMMANDS ============
 
@@ -37,6 +51,38 @@ async fn save_config(config: Config) -> Result<(), String> {
     .map_err(|e| e.to_string())?
 }
 
+/// Toggle the optional Discord Rich Presence integration, persisting the
+/// choice to config so it's remembered across restarts. Always available
+/// (so the frontend doesn't need to know whether `discord-presence` was
+/// compiled in); it's a no-op beyond the config write when it wasn't.
+#[tauri::command]
+async fn set_presence_enabled(enabled: bool) -> Result<(), String> {
+    let mut config = load_config().unwrap_or_default();
+    config.presence.enabled = enabled;
+    store_config(&config).map_err(|e| e.to_string())?;
+
+    #[cfg(feature = "discord-presence")]
+    presence::set_enabled(enabled).await;
+
+    Ok(())
+}
+
+// ============ BINARY RESOLVER COMMANDS ============
+
+/// Resolve (downloading/upgrading as needed) the bundled FFmpeg and yt-dlp
+/// binaries, emitting `setup-progress` events as it goes. Meant to be called
+/// once on first launch and otherwise whenever the frontend wants to re-check
+/// for updates; it's a no-op per tool whose cached version is already current.
+#[tauri::command]
+async fn resolve_binaries(window: tauri::Window) -> Result<BinaryStatus, String> {
+    do_resolve_binaries(window).await
+}
+
+#[tauri::command]
+fn get_binary_status() -> BinaryStatus {
+    load_binary_status()
+}
+
 // ============ EXTRACTOR COMMANDS ============
 
 #[tauri::command]
@@ -44,6 +90,24 @@ async fn extract_media(url: String) -> Result<MediaInfo, String> {
     do_extract(&url).await.map_err(|e| e.to_string())
 }
 
+/// Extract every part of a multi-part (分P) upload at once (e.g. a Bilibili
+/// video with multiple pages); sites without a page list return a
+/// single-element Vec.
+#[tauri::command]
+async fn extract_playlist(url: String) -> Result<Vec<MediaInfo>, String> {
+    extractor::extract_playlist(&url).await.map_err(|e| e.to_string())
+}
+
+/// Like `extract_media`, but resolves through yt-dlp directly so the
+/// frontend can reach its broader site coverage and playlist support on
+/// demand, with the `youtube_dl`-style knobs in `opts`.
+#[tauri::command]
+async fn extract_media_ytdlp(url: String, opts: Option<YtDlpOptions>) -> Result<YtDlpResult, String> {
+    do_extract_ytdlp(&url, opts.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // ============ FOLDER COMMANDS ============
 
 #[tauri::command]
@@ -88,6 +152,27 @@ async fn open_output_folder(path: String) -> Result<(), String> {
 
 // ============ DOWNLOAD COMMANDS ============
 
+/// One item to download, with the same per-item knobs `start_download`
+/// already took as flat arguments — reused so a playlist can queue many of
+/// these at once instead of the caller looping `start_download` itself.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct DownloadEntry {
+    url: String,
+    output_path: String,
+    headers: Option<std::collections::HashMap<String, String>>,
+    audio_url: Option<String>,
+    protocol: Option<String>,
+    height: Option<u32>,
+    /// For an HLS media playlist already resolved by the extractor (see
+    /// `Format::segments`): the ordered, absolute segment URLs, so
+    /// `HlsDownloader` doesn't have to re-fetch and re-parse the playlist.
+    segments: Option<Vec<String>>,
+    /// `#EXT-X-KEY:METHOD=AES-128` URI and IV for decrypting `segments`, if
+    /// the media playlist is encrypted.
+    key_uri: Option<String>,
+    key_iv: Option<String>,
+}
+
 #[tauri::command]
 async fn start_download(
     url: String,
@@ -95,9 +180,56 @@ async fn start_download(
     _format_id: Option<String>,
     headers: Option<std::collections::HashMap<String, String>>,
     audio_url: Option<String>,
+    protocol: Option<String>,
+    height: Option<u32>,
+    segments: Option<Vec<String>>,
+    key_uri: Option<String>,
+    key_iv: Option<String>,
+    playlist: Option<Vec<DownloadEntry>>,
     window: tauri::Window,
     download_manager: State<'_, Arc<DownloadManager>>,
-) -> Result<String, String> {
+) -> Result<Vec<String>, String> {
+    // A playlist extraction (see `extract_media_ytdlp`) supplies one entry
+    // per item; without one, fall back to the single item named by the flat
+    // arguments so existing single-download callers are unaffected.
+    let entries = playlist.unwrap_or_else(|| {
+        vec![DownloadEntry {
+            url,
+            output_path,
+            headers,
+            audio_url,
+            protocol,
+            height,
+            segments,
+            key_uri,
+            key_iv,
+        }]
+    });
+
+    let mut job_ids = Vec::with_capacity(entries.len());
+    for entry in entries {
+        job_ids.push(spawn_download_job(entry, window.clone(), download_manager.inner().clone()).await);
+    }
+
+    Ok(job_ids)
+}
+
+/// Queue one `DownloadEntry` as a `DownloadJob` and spawn its download task,
+/// returning the new job's id. Shared by every item `start_download` queues,
+/// whether there's one or a whole playlist's worth.
+async fn spawn_download_job(entry: DownloadEntry, window: tauri::Window, dm: Arc<DownloadManager>) -> String {
+    let DownloadEntry {
+        url,
+        output_path,
+        headers,
+        audio_url,
+        protocol,
+        height,
+        segments,
+        key_uri,
+        key_iv,
+    } = entry;
+
     let job_id = uuid::Uuid::new_v4().to_string();
 
     // Create job and get cancellation receiver
@@ -110,39 +242,56 @@ async fn start_download(
         error: None,
     };
 
-    let cancel_rx = download_manager.add_job(job).await;
+    let cancel_rx = dm.add_job(job).await;
 
     // Update status to downloading
-    download_manager
-        .update_job(&job_id, DownloadStatus::Downloading, None, None)
-        .await;
+    dm.update_job(&job_id, DownloadStatus::Downloading, None, None).await;
 
-    // Clone for async task
-    let dm = download_manager.inner().clone();
     let jid = job_id.clone();
 
     // Spawn download task
     tauri::async_runtime::spawn(async move {
-        let downloader = SimpleDownloader::new();
-
-        let result = if let Some(audio) = audio_url {
-            // DASH stream: download video + audio separately, then merge
-            downloader
-                .download_and_merge(
+        let result = if protocol.as_deref() == Some("m3u8") {
+            // HLS stream: use the extractor's already-resolved segment list
+            // (and AES-128 key, if encrypted) when available, falling back
+            // to resolving the playlist (and variant, if it's a master
+            // playlist) and deriving the segment list ourselves.
+            HlsDownloader::new()
+                .download(
                     &jid,
                     &url,
-                    &audio,
                     &output_path,
                     &window,
                     cancel_rx,
                     headers,
+                    height,
+                    segments,
+                    key_uri,
+                    key_iv,
                 )
                 .await
         } else {
-            // Simple download
-            downloader
-                .download(&jid, &url, &output_path, &window, cancel_rx, headers)
-                .await
+            let downloader = SimpleDownloader::new();
+
+            if let Some(audio) = audio_url {
+                // DASH stream: download video + audio separately, then merge
+                downloader
+                    .download_and_merge(
+                        &jid,
+                        &url,
+                        &audio,
+                        &output_path,
+                        &window,
+                        cancel_rx,
+                        headers,
+                    )
+                    .await
+            } else {
+                // Simple download
+                downloader
+                    .download(&jid, &url, &output_path, &window, cancel_rx, headers)
+                    .await
+            }
         };
 
         match result {
@@ -169,9 +318,14 @@ async fn start_download(
         }
     });
 
-    Ok(job_id)
+    job_id
 }
 
+/// Cancel `job_id`, whether it names a single download/conversion job or an
+/// entire batch queued by one of the `*_batch` commands. Items within a batch
+/// are jobs `DownloadManager` tracks too, but `run_bounded_batch` watches
+/// only the batch's own job, so cancelling one item's id neither stops nor
+/// skips that item.
 #[tauri::command]
 async fn cancel_download(
     job_id: String,
@@ -188,21 +342,232 @@ async fn get_download_status(
     Ok(download_manager.get_job(&job_id).await)
 }
 
+// ============ BATCH COMMANDS ============
+
+/// One file a batch command runs its shared operation over.
+#[derive(Debug, Clone, Deserialize)]
+struct BatchItem {
+    input: String,
+    output: String,
+}
+
+/// Aggregate progress for a whole batch, emitted after every item finishes
+/// (regardless of whether it succeeded).
+#[derive(Debug, Clone, Serialize)]
+struct BatchProgress {
+    #[serde(rename = "batchId")]
+    batch_id: String,
+    completed: u32,
+    total: u32,
+    failed: u32,
+}
+
+/// How many items of a single batch run concurrently.
+const MAX_BATCH_CONCURRENCY: usize = 4;
+
+/// Run `op` over every item in `items`, at most `MAX_BATCH_CONCURRENCY` at a
+/// time. The batch itself, and every item in it, is registered as a job in
+/// `DownloadManager` (so `get_download_status` can see them, and
+/// `cancel_download` on the batch's job cancels the batch) instead of tracked by a parallel
+/// mechanism — the batch's own job is what `*_batch` commands return as
+/// `batch_id`, and `run_bounded_batch` skips starting any item once it's
+/// cancelled. `batch-progress` is still emitted after each item finishes.
+async fn run_bounded_batch<F, Fut>(
+    items: Vec<BatchItem>,
+    batch_id: String,
+    download_manager: Arc<DownloadManager>,
+    window: tauri::Window,
+    op: F,
+) where
+    F: Fn(BatchItem, String, tauri::Window) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Result<(), String>> + Send + 'static,
+{
+    let total = items.len() as u32;
+    let completed = Arc::new(AtomicU32::new(0));
+    let failed = Arc::new(AtomicU32::new(0));
+    let semaphore = Arc::new(Semaphore::new(MAX_BATCH_CONCURRENCY));
+    let op = Arc::new(op);
+
+    let batch_cancel_rx = download_manager
+        .add_job(DownloadJob {
+            id: batch_id.clone(),
+            url: format!("batch of {} item(s)", total),
+            output_path: String::new(),
+            status: DownloadStatus::Pending,
+            progress: None,
+            error: None,
+        })
+        .await;
+    download_manager
+        .update_job(&batch_id, DownloadStatus::Downloading, Some(0.0), None)
+        .await;
+
+    let mut tasks = Vec::with_capacity(items.len());
+    for item in items {
+        let permit = semaphore.clone().acquire_owned().await.expect("batch semaphore closed");
+        let job_id = uuid::Uuid::new_v4().to_string();
+        let op = op.clone();
+        let window = window.clone();
+        let batch_id = batch_id.clone();
+        let completed = completed.clone();
+        let failed = failed.clone();
+        let cancel_rx = batch_cancel_rx.clone();
+        let dm = download_manager.clone();
+
+        dm.add_job(DownloadJob {
+            id: job_id.clone(),
+            url: item.input.clone(),
+            output_path: item.output.clone(),
+            status: DownloadStatus::Pending,
+            progress: None,
+            error: None,
+        })
+        .await;
+        dm.update_job(&job_id, DownloadStatus::Downloading, None, None).await;
+
+        tasks.push(tokio::spawn(async move {
+            let _permit = permit;
+
+            if *cancel_rx.borrow() {
+                dm.update_job(&job_id, DownloadStatus::Cancelled, None, None).await;
+                failed.fetch_add(1, Ordering::Relaxed);
+            } else if let Err(e) = op(item, job_id.clone(), window.clone()).await {
+                dm.update_job(&job_id, DownloadStatus::Failed, None, Some(e.clone())).await;
+                failed.fetch_add(1, Ordering::Relaxed);
+                let _ = window.emit("ffmpeg-error", serde_json::json!({ "jobId": job_id, "error": e }));
+            } else {
+                dm.update_job(&job_id, DownloadStatus::Completed, None, None).await;
+            }
+
+            let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+            let pct = (done as f32 / total as f32) * 100.0;
+            dm.update_job(&batch_id, DownloadStatus::Downloading, Some(pct), None).await;
+            let _ = window.emit(
+                "batch-progress",
+                &BatchProgress {
+                    batch_id,
+                    completed: done,
+                    total,
+                    failed: failed.load(Ordering::Relaxed),
+                },
+            );
+        }));
+    }
+
+    for task in tasks {
+        let _ = task.await;
+    }
+
+    let final_status = if failed.load(Ordering::Relaxed) == total && total > 0 {
+        DownloadStatus::Failed
+    } else {
+        DownloadStatus::Completed
+    };
+    download_manager
+        .update_job(&batch_id, final_status, Some(100.0), None)
+        .await;
+}
+
+// ============ WEBDAV COMMANDS ============
+
+#[tauri::command]
+async fn upload_to_webdav(
+    server_name: String,
+    local_path: String,
+    remote_subdir: String,
+    window: tauri::Window,
+) -> Result<(), String> {
+    let job_id = uuid::Uuid::new_v4().to_string();
+    webdav::upload_to_webdav(&job_id, &server_name, &local_path, &remote_subdir, &window).await
+}
+
+// ============ PREVIEW COMMANDS ============
+
+/// Whitelist `path` with the local preview server and return a
+/// `http://127.0.0.1:{port}/{token}` URL a `<video>`/`<audio>` element can
+/// point at directly, including for seeking (the server honors `Range`).
+#[tauri::command]
+async fn preview_register(path: String, preview_registry: State<'_, Arc<PreviewRegistry>>) -> Result<String, String> {
+    preview_registry.register(PathBuf::from(path)).await
+}
+
+/// Revoke a URL previously returned by `preview_register` so the server
+/// stops serving it.
+#[tauri::command]
+async fn preview_unregister(url: String, preview_registry: State<'_, Arc<PreviewRegistry>>) -> Result<(), String> {
+    preview_registry.unregister(&url).await;
+    Ok(())
+}
+
 // ============ FFMPEG MEDIA TOOLS ============
 
+/// Probe `input_path` and reject it against the user's configured
+/// `ffmpeg_limits` before a command spawns the real job. Called as the first
+/// step of every job-running `ffmpeg_*` command below (`ffmpeg_get_media_info`
+/// and `ffmpeg_validate_for_merge` only probe and skip it); on rejection the error string is
+/// `PreflightRejection`'s JSON (`{"reason": "...", "detail": "..."}`) so the
+/// frontend can switch on `reason` instead of pattern-matching a message.
+async fn ffmpeg_preflight(input_path: &str) -> Result<ffmpeg::MediaProbe, String> {
+    let config = load_config().unwrap_or_default();
+    ffmpeg::preflight(input_path, &config.ffmpeg_limits)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Push ffmpeg progress into Discord presence (`label`/`target`, e.g.
+/// `"Converting"`/`"clip.mp4"`), converting `elapsed_secs` into a percentage
+/// when `duration` (from the preflight probe) is known. No-op unless the
+/// `discord-presence` feature is compiled in.
+fn report_ffmpeg_progress(_label: &'static str, _target: &str, _elapsed_secs: f32, _duration: Option<f64>) {
+    #[cfg(feature = "discord-presence")]
+    {
+        let target = _target.to_string();
+        let progress = _duration
+            .filter(|d| *d > 0.0)
+            .map(|d| ((_elapsed_secs as f64 / d) * 100.0).clamp(0.0, 100.0) as f32);
+        tauri::async_runtime::spawn(async move {
+            presence::ffmpeg_set_activity(_label, &target, progress).await;
+        });
+    }
+}
+
+/// Clear the ffmpeg job presence is currently showing, e.g. once a job
+/// completes, fails, or is cancelled. No-op unless `discord-presence` is
+/// compiled in.
+fn clear_ffmpeg_activity() {
+    #[cfg(feature = "discord-presence")]
+    tauri::async_runtime::spawn(presence::ffmpeg_clear_activity());
+}
+
 #[tauri::command]
 async fn ffmpeg_get_media_info(input_path: String) -> Result<MediaInfoResult, String> {
     ffmpeg::get_media_info(&input_path).await
 }
 
+#[tauri::command]
+async fn ffmpeg_validate_for_merge(
+    video_path: String,
+    audio_path: String,
+    target_container: String,
+    limits: Option<ffmpeg::MergeLimits>,
+) -> Result<ffmpeg::MergePlan, String> {
+    ffmpeg::validate_for_merge(&video_path, &audio_path, &target_container, limits.as_ref()).await
+}
+
 #[tauri::command]
 async fn ffmpeg_convert_video(
     input_path: String,
     output_path: String,
     window: tauri::Window,
 ) -> Result<String, String> {
+    let probe = ffmpeg_preflight(&input_path).await?;
     let job_id = uuid::Uuid::new_v4().to_string();
     let jid = job_id.clone();
+    let input_name = std::path::Path::new(&input_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&input_path)
+        .to_string();
 
     tauri::async_runtime::spawn(async move {
         let result = tokio::task::spawn_blocking({
@@ -210,9 +575,11 @@ async fn ffmpeg_convert_video(
             let output = output_path.clone();
             let jid = jid.clone();
             let win = window.clone();
+            let duration = probe.duration;
 
             move || {
                 ffmpeg::convert_video_sync(&input, &output, move |progress| {
+                    report_ffmpeg_progress("Converting", &input_name, progress, duration);
                     let _ = win.emit(
                         "ffmpeg-progress",
                         serde_json::json!({
@@ -224,6 +591,7 @@ async fn ffmpeg_convert_video(
             }
         })
         .await;
+        clear_ffmpeg_activity();
 
         match result {
             Ok(Ok(())) => {
@@ -259,6 +627,41 @@ async fn ffmpeg_convert_video(
     Ok(job_id)
 }
 
+/// Convert every `{input, output}` pair in `items`, up to
+/// `MAX_BATCH_CONCURRENCY` at a time. Each item emits its own
+/// `ffmpeg-progress`/`ffmpeg-error` keyed by a per-item job id; `batch-progress`
+/// reports aggregate completion, and the whole batch can be cancelled at once
+/// via `cancel_download(batchId)`.
+#[tauri::command]
+async fn ffmpeg_convert_video_batch(
+    items: Vec<BatchItem>,
+    window: tauri::Window,
+    download_manager: State<'_, Arc<DownloadManager>>,
+) -> Result<String, String> {
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    let dm = download_manager.inner().clone();
+    let bid = batch_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        run_bounded_batch(items, bid, dm, window, |item, job_id, window| async move {
+            ffmpeg_preflight(&item.input).await?;
+            tokio::task::spawn_blocking(move || {
+                ffmpeg::convert_video_sync(&item.input, &item.output, move |progress| {
+                    let _ = window.emit(
+                        "ffmpeg-progress",
+                        serde_json::json!({ "jobId": job_id, "progress": progress }),
+                    );
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())?
+        })
+        .await;
+    });
+
+    Ok(batch_id)
+}
+
 #[tauri::command]
 async fn ffmpeg_compress_video(
     input_path: String,
@@ -266,8 +669,14 @@ async fn ffmpeg_compress_video(
     quality: u8, // CRF value: 18 (high quality) to 28 (low quality/small size)
     window: tauri::Window,
 ) -> Result<String, String> {
+    let probe = ffmpeg_preflight(&input_path).await?;
     let job_id = uuid::Uuid::new_v4().to_string();
     let jid = job_id.clone();
+    let input_name = std::path::Path::new(&input_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&input_path)
+        .to_string();
 
     tauri::async_runtime::spawn(async move {
         let result = tokio::task::spawn_blocking({
@@ -275,9 +684,11 @@ async fn ffmpeg_compress_video(
             let output = output_path.clone();
             let jid = jid.clone();
             let win = window.clone();
+            let duration = probe.duration;
 
             move || {
                 ffmpeg::compress_video_sync(&input, &output, quality, move |progress| {
+                    report_ffmpeg_progress("Compressing", &input_name, progress, duration);
                     let _ = win.emit(
                         "ffmpeg-progress",
                         serde_json::json!({
@@ -289,6 +700,7 @@ async fn ffmpeg_compress_video(
             }
         })
         .await;
+        clear_ffmpeg_activity();
 
         match result {
             Ok(Ok(())) => {
@@ -324,6 +736,179 @@ async fn ffmpeg_compress_video(
     Ok(job_id)
 }
 
+/// Batch form of `ffmpeg_compress_video`: same shared `quality` for every
+/// `{input, output}` pair in `items`. See `ffmpeg_convert_video_batch` for
+/// the progress/cancellation contract.
+#[tauri::command]
+async fn ffmpeg_compress_video_batch(
+    items: Vec<BatchItem>,
+    quality: u8,
+    window: tauri::Window,
+    download_manager: State<'_, Arc<DownloadManager>>,
+) -> Result<String, String> {
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    let dm = download_manager.inner().clone();
+    let bid = batch_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        run_bounded_batch(items, bid, dm, window, move |item, job_id, window| async move {
+            ffmpeg_preflight(&item.input).await?;
+            tokio::task::spawn_blocking(move || {
+                ffmpeg::compress_video_sync(&item.input, &item.output, quality, move |progress| {
+                    let _ = window.emit(
+                        "ffmpeg-progress",
+                        serde_json::json!({ "jobId": job_id, "progress": progress }),
+                    );
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())?
+        })
+        .await;
+    });
+
+    Ok(batch_id)
+}
+
+#[tauri::command]
+async fn ffmpeg_compress_video_target_vmaf(
+    input_path: String,
+    output_path: String,
+    target_vmaf: f32,
+    window: tauri::Window,
+) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let jid = job_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let result = tokio::task::spawn_blocking({
+            let input = input_path.clone();
+            let output = output_path.clone();
+            let jid = jid.clone();
+            let win = window.clone();
+
+            move || {
+                ffmpeg::compress_video_target_vmaf_sync(&input, &output, target_vmaf, move |progress| {
+                    let _ = win.emit(
+                        "ffmpeg-progress",
+                        serde_json::json!({
+                            "jobId": jid,
+                            "progress": progress,
+                        }),
+                    );
+                })
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Ok(vmaf_result)) => {
+                let _ = window.emit(
+                    "ffmpeg-complete",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "outputPath": output_path,
+                        "crf": vmaf_result.crf,
+                        "vmaf": vmaf_result.vmaf,
+                    }),
+                );
+            }
+            Ok(Err(e)) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e,
+                    }),
+                );
+            }
+            Err(e) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e.to_string(),
+                    }),
+                );
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
+#[tauri::command]
+async fn ffmpeg_encode_chunked(
+    input_path: String,
+    output_path: String,
+    settings: ffmpeg::ChunkEncodeSettings,
+    workers: Option<usize>,
+    window: tauri::Window,
+) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let jid = job_id.clone();
+    let workers = workers.unwrap_or_else(|| {
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+    });
+
+    tauri::async_runtime::spawn(async move {
+        let result = tokio::task::spawn_blocking({
+            let input = input_path.clone();
+            let output = output_path.clone();
+            let jid = jid.clone();
+            let win = window.clone();
+
+            move || {
+                ffmpeg::encode_chunked(&input, &output, settings, workers, move |progress| {
+                    let _ = win.emit(
+                        "ffmpeg-progress",
+                        serde_json::json!({
+                            "jobId": jid,
+                            "progress": progress,
+                        }),
+                    );
+                })
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Ok(timings)) => {
+                let _ = window.emit(
+                    "ffmpeg-complete",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "outputPath": output_path,
+                        "chunks": timings,
+                    }),
+                );
+            }
+            Ok(Err(e)) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e,
+                    }),
+                );
+            }
+            Err(e) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e.to_string(),
+                    }),
+                );
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
 #[tauri::command]
 async fn ffmpeg_trim_video(
     input_path: String,
@@ -332,6 +917,7 @@ async fn ffmpeg_trim_video(
     end_time: String,
     window: tauri::Window,
 ) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
     let job_id = uuid::Uuid::new_v4().to_string();
     let jid = job_id.clone();
 
@@ -392,6 +978,124 @@ async fn ffmpeg_trim_video(
     Ok(job_id)
 }
 
+#[tauri::command]
+async fn ffmpeg_respeed_video(
+    input_path: String,
+    output_path: String,
+    ranges: Vec<(f64, f64, f32)>,
+    window: tauri::Window,
+) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let jid = job_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let result = tokio::task::spawn_blocking({
+            let input = input_path.clone();
+            let output = output_path.clone();
+            let jid = jid.clone();
+            let win = window.clone();
+
+            move || {
+                ffmpeg::respeed_video_sync(&input, &output, ranges, move |progress| {
+                    let _ = win.emit(
+                        "ffmpeg-progress",
+                        serde_json::json!({
+                            "jobId": jid,
+                            "progress": progress,
+                        }),
+                    );
+                })
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {
+                let _ = window.emit(
+                    "ffmpeg-complete",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "outputPath": output_path,
+                    }),
+                );
+            }
+            Ok(Err(e)) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e,
+                    }),
+                );
+            }
+            Err(e) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e.to_string(),
+                    }),
+                );
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
+#[tauri::command]
+async fn ffmpeg_assemble_timeline(
+    clips: Vec<ffmpeg::ClipSpec>,
+    output_path: String,
+    transition: ffmpeg::Transition,
+    window: tauri::Window,
+) -> Result<String, String> {
+    for clip in &clips {
+        ffmpeg_preflight(&clip.path).await?;
+    }
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let jid = job_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let jid_progress = jid.clone();
+        let win = window.clone();
+        let result = ffmpeg::assemble_timeline(clips, &output_path, transition, move |progress| {
+            let _ = win.emit(
+                "ffmpeg-progress",
+                serde_json::json!({
+                    "jobId": jid_progress,
+                    "progress": progress,
+                }),
+            );
+        })
+        .await;
+
+        match result {
+            Ok(()) => {
+                let _ = window.emit(
+                    "ffmpeg-complete",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "outputPath": output_path,
+                    }),
+                );
+            }
+            Err(e) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e,
+                    }),
+                );
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
 #[tauri::command]
 async fn ffmpeg_extract_audio(
     input_path: String,
@@ -399,6 +1103,7 @@ async fn ffmpeg_extract_audio(
     format: String, // mp3, aac, flac, wav
     window: tauri::Window,
 ) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
     let job_id = uuid::Uuid::new_v4().to_string();
     let jid = job_id.clone();
 
@@ -465,6 +1170,7 @@ async fn ffmpeg_extract_frames(
     fps: f32,
     window: tauri::Window,
 ) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
     let job_id = uuid::Uuid::new_v4().to_string();
     let jid = job_id.clone();
 
@@ -524,6 +1230,154 @@ async fn ffmpeg_extract_frames(
     Ok(job_id)
 }
 
+#[tauri::command]
+async fn ffmpeg_segment_hls(
+    input_path: String,
+    output_dir: String,
+    seconds_per_segment: u32,
+    reencode_for_seeking: bool,
+    window: tauri::Window,
+) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let jid = job_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let result = tokio::task::spawn_blocking({
+            let input = input_path.clone();
+            let output = output_dir.clone();
+            let jid = jid.clone();
+            let win = window.clone();
+
+            move || {
+                ffmpeg::segment_hls_sync(
+                    &input,
+                    &output,
+                    seconds_per_segment,
+                    reencode_for_seeking,
+                    move |progress| {
+                        let _ = win.emit(
+                            "ffmpeg-progress",
+                            serde_json::json!({
+                                "jobId": jid,
+                                "progress": progress,
+                            }),
+                        );
+                    },
+                )
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Ok(segmented)) => {
+                let _ = window.emit(
+                    "ffmpeg-complete",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "manifestPath": segmented.manifest_path,
+                        "segmentFiles": segmented.segment_files,
+                    }),
+                );
+            }
+            Ok(Err(e)) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e,
+                    }),
+                );
+            }
+            Err(e) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e.to_string(),
+                    }),
+                );
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
+#[tauri::command]
+async fn ffmpeg_segment_dash(
+    input_path: String,
+    output_dir: String,
+    seconds_per_segment: u32,
+    reencode_for_seeking: bool,
+    window: tauri::Window,
+) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
+    let job_id = uuid::Uuid::new_v4().to_string();
+    let jid = job_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let result = tokio::task::spawn_blocking({
+            let input = input_path.clone();
+            let output = output_dir.clone();
+            let jid = jid.clone();
+            let win = window.clone();
+
+            move || {
+                ffmpeg::segment_dash_sync(
+                    &input,
+                    &output,
+                    seconds_per_segment,
+                    reencode_for_seeking,
+                    move |progress| {
+                        let _ = win.emit(
+                            "ffmpeg-progress",
+                            serde_json::json!({
+                                "jobId": jid,
+                                "progress": progress,
+                            }),
+                        );
+                    },
+                )
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Ok(segmented)) => {
+                let _ = window.emit(
+                    "ffmpeg-complete",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "manifestPath": segmented.manifest_path,
+                        "segmentFiles": segmented.segment_files,
+                    }),
+                );
+            }
+            Ok(Err(e)) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e,
+                    }),
+                );
+            }
+            Err(e) => {
+                let _ = window.emit(
+                    "ffmpeg-error",
+                    serde_json::json!({
+                        "jobId": jid,
+                        "error": e.to_string(),
+                    }),
+                );
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
 #[tauri::command]
 async fn ffmpeg_convert_audio(
     input_path: String,
@@ -532,6 +1386,7 @@ async fn ffmpeg_convert_audio(
     bitrate: Option<String>,
     window: tauri::Window,
 ) -> Result<String, String> {
+    ffmpeg_preflight(&input_path).await?;
     let job_id = uuid::Uuid::new_v4().to_string();
     let jid = job_id.clone();
 
@@ -628,6 +1483,36 @@ async fn pdf_delete_pages(
     .map_err(|e| e.to_string())?
 }
 
+/// Batch form of `pdf_delete_pages`: the same `pages` removed from every
+/// `{input, output}` pair in `items`. See `ffmpeg_convert_video_batch` for
+/// the progress/cancellation contract (pages don't report per-file progress,
+/// only `batch-progress` completion).
+#[tauri::command]
+async fn pdf_delete_pages_batch(
+    items: Vec<BatchItem>,
+    pages: Vec<u32>,
+    window: tauri::Window,
+    download_manager: State<'_, Arc<DownloadManager>>,
+) -> Result<String, String> {
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    let dm = download_manager.inner().clone();
+    let bid = batch_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        run_bounded_batch(items, bid, dm, window, move |item, _job_id, _window| {
+            let pages = pages.clone();
+            async move {
+                tauri::async_runtime::spawn_blocking(move || pdf::delete_pages(&item.input, &item.output, &pages))
+                    .await
+                    .map_err(|e| e.to_string())?
+            }
+        })
+        .await;
+    });
+
+    Ok(batch_id)
+}
+
 #[tauri::command]
 async fn pdf_remove_watermark(
     input_path: String,
@@ -638,6 +1523,32 @@ async fn pdf_remove_watermark(
         .map_err(|e| e.to_string())?
 }
 
+/// Batch form of `pdf_remove_watermark` over every `{input, output}` pair in
+/// `items`; per-file watermark-detection details aren't surfaced in batch
+/// mode, only success/failure and the aggregate `batch-progress`.
+#[tauri::command]
+async fn pdf_remove_watermark_batch(
+    items: Vec<BatchItem>,
+    window: tauri::Window,
+    download_manager: State<'_, Arc<DownloadManager>>,
+) -> Result<String, String> {
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    let dm = download_manager.inner().clone();
+    let bid = batch_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        run_bounded_batch(items, bid, dm, window, |item, _job_id, _window| async move {
+            tauri::async_runtime::spawn_blocking(move || pdf::remove_watermark(&item.input, &item.output))
+                .await
+                .map_err(|e| e.to_string())??;
+            Ok(())
+        })
+        .await;
+    });
+
+    Ok(batch_id)
+}
+
 #[tauri::command]
 async fn pdf_print(input_path: String) -> Result<(), String> {
     tauri::async_runtime::spawn_blocking(move || pdf::print_pdf(&input_path))
@@ -666,14 +1577,51 @@ async fn read_text_file(path: String) -> Result<String, String> {
 // ============ MARKDOWN TO PDF ============
 
 #[tauri::command]
-async fn md_to_pdf(
+async fn convert_markdown(
     input_path: String,
     output_path: String,
     theme: String,
     page_size: String,
+    toc: Option<bool>,
+    toc_max_depth: Option<u8>,
+    custom_css_path: Option<String>,
+    syntax_theme_path: Option<String>,
+    format: Option<String>,
+    header_footer: Option<bool>,
+    footer_text: Option<String>,
+    page_number_position: Option<String>,
+    line_numbers: Option<bool>,
+    embed_fonts: Option<bool>,
+    copy_button: Option<bool>,
+    margin_in: Option<f64>,
+    input_format: Option<String>,
 ) -> Result<(), String> {
+    let format = match format.as_deref() {
+        Some("html") => md2pdf::OutputFormat::Html,
+        Some("epub") => md2pdf::OutputFormat::Epub,
+        _ => md2pdf::OutputFormat::Pdf,
+    };
+
     tauri::async_runtime::spawn_blocking(move || {
-        md2pdf::convert_md_to_pdf(&input_path, &output_path, &theme, &page_size)
+        md2pdf::convert_markdown(
+            &input_path,
+            &output_path,
+            &theme,
+            &page_size,
+            toc.unwrap_or(false),
+            toc_max_depth,
+            custom_css_path.as_deref(),
+            syntax_theme_path.as_deref(),
+            format,
+            header_footer.unwrap_or(false),
+            footer_text.as_deref(),
+            page_number_position.as_deref().unwrap_or("center"),
+            line_numbers.unwrap_or(false),
+            embed_fonts.unwrap_or(true),
+            copy_button.unwrap_or(false),
+            margin_in,
+            input_format.as_deref(),
+        )
     })
     .await
     .map_err(|e| e.to_string())?
@@ -683,24 +1631,44 @@ async fn md_to_pdf(
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let download_manager = Arc::new(DownloadManager::new());
+
+    #[cfg(feature = "discord-presence")]
+    {
+        presence::init_enabled(load_config().unwrap_or_default().presence.enabled);
+        presence::spawn_presence_loop(download_manager.clone());
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
-        .manage(Arc::new(DownloadManager::new()))
+        .manage(download_manager)
+        .manage(Arc::new(PreviewRegistry::new()))
         .invoke_handler(tauri::generate_handler![
             // Config
             get_config,
             save_config,
+            set_presence_enabled,
+            // Binary resolver
+            resolve_binaries,
+            get_binary_status,
             // Extractor
             extract_media,
+            extract_playlist,
+            extract_media_ytdlp,
             // Folder
             open_output_folder,
             // Download
             start_download,
             cancel_download,
             get_download_status,
+            // WebDAV
+            upload_to_webdav,
+            // Preview
+            preview_register,
+            preview_unregister,
             // Auth - Bilibili
             bilibili_check_status,
             bilibili_qr_generate,
@@ -713,24 +1681,35 @@ pub fn run() {
             xhs_open_login_window,
             // FFmpeg Media Tools
             ffmpeg_get_media_info,
+            ffmpeg_validate_for_merge,
             ffmpeg_convert_video,
+            ffmpeg_convert_video_batch,
             ffmpeg_compress_video,
+            ffmpeg_compress_video_batch,
+            ffmpeg_compress_video_target_vmaf,
+            ffmpeg_encode_chunked,
             ffmpeg_trim_video,
+            ffmpeg_respeed_video,
+            ffmpeg_assemble_timeline,
             ffmpeg_extract_audio,
             ffmpeg_extract_frames,
+            ffmpeg_segment_hls,
+            ffmpeg_segment_dash,
             ffmpeg_convert_audio,
             // PDF Tools
             pdf_get_info,
             pdf_merge,
             pdf_images_to_pdf,
             pdf_delete_pages,
+            pdf_delete_pages_batch,
             pdf_remove_watermark,
+            pdf_remove_watermark_batch,
             pdf_print,
             pdf_open_external,
             // File utilities
             read_text_file,
             // Markdown to PDF
-            md_to_pdf,
+            convert_markdown,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
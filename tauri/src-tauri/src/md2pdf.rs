@@ -1,11 +1,17 @@
 use base64::{engine::general_purpose::STANDARD, Engine};
 use headless_chrome::{types::PrintToPdfOptions, Browser, LaunchOptions};
 use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::collections::HashMap;
 use std::fs;
+use std::io::Write as _;
 use std::path::Path;
-use syntect::highlighting::ThemeSet;
-use syntect::html::highlighted_html_for_string;
-use syntect::parsing::SyntaxSet;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme as SyntaxTheme, ThemeSet};
+use syntect::html::{highlighted_html_for_string, styled_line_to_highlighted_html, IncludeBackground};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
 
 // Embed fonts at compile time
 static INTER_REGULAR: &[u8] = include_bytes!("../resources/fonts/Inter-Regular.woff2");
@@ -16,31 +22,184 @@ static INTER_EXTRABOLD: &[u8] = include_bytes!("../resources/fonts/Inter-ExtraBo
 static JETBRAINS_MONO_REGULAR: &[u8] = include_bytes!("../resources/fonts/JetBrainsMono-Regular.woff2");
 static JETBRAINS_MONO_MEDIUM: &[u8] = include_bytes!("../resources/fonts/JetBrainsMono-Medium.woff2");
 
-/// Convert markdown file to PDF
-pub fn convert_md_to_pdf(
+/// Output container for [`convert_markdown`], following the same source
+/// parse through to whichever writer the caller asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Pdf,
+    Html,
+    Epub,
+}
+
+/// Built-in stylesheet presets for the `.markdown-body` output, selected by
+/// name (e.g. `theme` on [`convert_markdown`]'s tauri command, or
+/// `--theme teal` on a future CLI front-end).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Light,
+    Slate,
+    Azure,
+    Teal,
+    /// Follows the viewer's OS `prefers-color-scheme` instead of a fixed
+    /// palette: a dark `:root` overridden by a light one under the media
+    /// query, so the same exported file adapts at view time.
+    Auto,
+}
+
+impl Theme {
+    /// Resolve a theme name, defaulting to `Light` for anything unrecognized.
+    /// `"dark"` is kept as an alias for `Slate` for backwards compatibility.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "dark" | "slate" => Theme::Slate,
+            "azure" => Theme::Azure,
+            "teal" => Theme::Teal,
+            "auto" => Theme::Auto,
+            _ => Theme::Light,
+        }
+    }
+
+    /// Whether this theme's background is dark, used to pick a matching
+    /// syntect syntax-highlighting theme. `Auto`'s `:root` default is dark,
+    /// so it picks the dark syntax theme too.
+    fn is_dark(self) -> bool {
+        !matches!(self, Theme::Light)
+    }
+
+    fn css(self) -> &'static str {
+        match self {
+            Theme::Light => LIGHT_THEME_CSS,
+            Theme::Slate => DARK_THEME_CSS,
+            Theme::Azure => AZURE_THEME_CSS,
+            Theme::Teal => TEAL_THEME_CSS,
+            Theme::Auto => ADAPTIVE_THEME_CSS,
+        }
+    }
+}
+
+/// Convert a Markdown or AsciiDoc file to `Pdf`, standalone `Html`, or a
+/// reflowable `Epub`, sharing the same parse and styling for all three.
+/// `input_format` selects the front end (`"markdown"` or `"asciidoc"`);
+/// `None` auto-detects from the file extension (or a leading `= Title`
+/// document header) via [`crate::asciidoc::looks_like_asciidoc`].
+pub fn convert_markdown(
     input_path: &str,
     output_path: &str,
     theme: &str,
     page_size: &str,
+    toc: bool,
+    toc_max_depth: Option<u8>,
+    custom_css_path: Option<&str>,
+    syntax_theme_path: Option<&str>,
+    format: OutputFormat,
+    header_footer: bool,
+    footer_text: Option<&str>,
+    page_number_position: &str,
+    line_numbers: bool,
+    embed_fonts: bool,
+    copy_button: bool,
+    margin_in: Option<f64>,
+    input_format: Option<&str>,
 ) -> Result<(), String> {
-    // Read markdown file
-    let markdown = fs::read_to_string(input_path)
-        .map_err(|e| format!("Failed to read markdown file: {}", e))?;
-
-    // Parse markdown to HTML with syntax highlighting
-    let html_content = markdown_to_html(&markdown, theme);
-
-    // Generate full HTML with styling
-    let full_html = generate_styled_html(&html_content, theme);
+    // Read markdown (or AsciiDoc) file
+    let input_text = fs::read_to_string(input_path)
+        .map_err(|e| format!("Failed to read input file: {}", e))?;
+
+    let is_asciidoc = match input_format {
+        Some("asciidoc") => true,
+        Some(_) => false,
+        None => crate::asciidoc::looks_like_asciidoc(input_path, &input_text),
+    };
 
-    // Convert HTML to PDF using headless Chrome
-    html_to_pdf(&full_html, output_path, page_size)?;
+    // Parse the document to HTML with syntax highlighting. Both front ends
+    // produce the same `.markdown-body` fragment plus TOC/title, so every
+    // downstream step (styling, PDF/HTML/EPUB emission) is shared.
+    let (html_content, toc_html, toc_entries, document_title) = if is_asciidoc {
+        crate::asciidoc::asciidoc_to_document(&input_text, theme, toc, toc_max_depth)
+    } else {
+        markdown_to_html(
+            &input_text,
+            theme,
+            toc,
+            toc_max_depth,
+            syntax_theme_path,
+            line_numbers,
+            copy_button,
+        )?
+    };
 
-    Ok(())
+    // Both the PDF header and the EPUB metadata fall back to the input
+    // file's stem, then to "Document", when the document has no `h1`.
+    let title = document_title.unwrap_or_else(|| {
+        Path::new(input_path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Document".to_string())
+    });
+
+    match format {
+        OutputFormat::Pdf => {
+            let full_html = generate_styled_html(
+                &html_content,
+                theme,
+                toc_html.as_deref(),
+                custom_css_path,
+                embed_fonts,
+                copy_button,
+            )?;
+            html_to_pdf(
+                &full_html,
+                output_path,
+                page_size,
+                &title,
+                header_footer,
+                footer_text,
+                page_number_position,
+                margin_in,
+            )
+        }
+        OutputFormat::Html => {
+            let full_html = generate_styled_html(
+                &html_content,
+                theme,
+                toc_html.as_deref(),
+                custom_css_path,
+                embed_fonts,
+                copy_button,
+            )?;
+            fs::write(output_path, full_html).map_err(|e| format!("Failed to write HTML file: {}", e))
+        }
+        OutputFormat::Epub => write_epub(
+            &html_content,
+            theme,
+            toc_html.as_deref(),
+            custom_css_path,
+            &toc_entries,
+            output_path,
+            &title,
+        ),
+    }
 }
 
-/// Parse markdown to HTML using pulldown-cmark with syntax highlighting
-fn markdown_to_html(markdown: &str, theme: &str) -> String {
+/// Parse markdown to HTML using pulldown-cmark with syntax highlighting. When
+/// `toc` is set, also returns a `<nav id="TOC">` block with a nested list of
+/// heading links (capped at `toc_max_depth`, default all levels), and each
+/// heading gets a slugified `id` so the links resolve. `syntax_theme_path`,
+/// when set, loads a `.tmTheme` file in place of the bundled syntect themes.
+/// `line_numbers` renders a gutter line counter on every code block (fenced
+/// blocks can also carry a `{1,4-6}` line-range suffix on the info string,
+/// which highlights those lines regardless of this flag).
+/// The final element is the document's title, taken from the first `h1`
+/// encountered (`None` if the document has none).
+fn markdown_to_html(
+    markdown: &str,
+    theme: &str,
+    toc: bool,
+    toc_max_depth: Option<u8>,
+    syntax_theme_path: Option<&str>,
+    line_numbers: bool,
+    copy_button: bool,
+) -> Result<(String, Option<String>, Vec<TocEntry>, Option<String>), String> {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_FOOTNOTES);
@@ -53,7 +212,12 @@ fn markdown_to_html(markdown: &str, theme: &str) -> String {
     // Load syntax highlighting
     let ss = SyntaxSet::load_defaults_newlines();
     let ts = ThemeSet::load_defaults();
-    let syntax_theme = if theme == "dark" {
+    let loaded_theme;
+    let syntax_theme = if let Some(path) = syntax_theme_path {
+        loaded_theme = ThemeSet::get_theme(path)
+            .map_err(|e| format!("Failed to load syntax theme '{}': {}", path, e))?;
+        &loaded_theme
+    } else if Theme::parse(theme).is_dark() {
         &ts.themes["base16-ocean.dark"]
     } else {
         &ts.themes["InspiredGitHub"]
@@ -64,16 +228,39 @@ fn markdown_to_html(markdown: &str, theme: &str) -> String {
     let mut in_table_head = false;
     let mut code_lang = String::new();
     let mut code_content = String::new();
+    let mut code_highlight_ranges: Vec<(usize, usize)> = Vec::new();
+
+    // While inside a heading, markup is buffered separately so we know the
+    // heading's full text (for the slug) before writing the opening tag.
+    let mut in_heading = false;
+    let mut heading_level: u8 = 0;
+    let mut heading_html = String::new();
+    let mut heading_text = String::new();
+    let mut toc_entries: Vec<TocEntry> = Vec::new();
+    let mut slug_counts: HashMap<String, u32> = HashMap::new();
+    let mut document_title: Option<String> = None;
 
     for event in parser {
+        if in_heading {
+            match &event {
+                Event::Start(Tag::Heading { .. }) | Event::End(TagEnd::Heading(_)) => {}
+                Event::Text(text) | Event::Code(text) => heading_text.push_str(text),
+                Event::SoftBreak | Event::HardBreak => heading_text.push(' '),
+                _ => {}
+            }
+        }
+        let out = if in_heading { &mut heading_html } else { &mut html_output };
         match event {
             Event::Start(Tag::CodeBlock(kind)) => {
                 in_code_block = true;
                 code_content.clear();
-                code_lang = match kind {
-                    CodeBlockKind::Fenced(lang) => lang.to_string(),
+                let info_string = match kind {
+                    CodeBlockKind::Fenced(info) => info.to_string(),
                     CodeBlockKind::Indented => String::new(),
                 };
+                let (lang, ranges) = parse_fence_info(&info_string);
+                code_lang = lang;
+                code_highlight_ranges = ranges;
             }
             Event::End(TagEnd::CodeBlock) => {
                 in_code_block = false;
@@ -85,127 +272,175 @@ fn markdown_to_html(markdown: &str, theme: &str) -> String {
                 }
                 .unwrap_or_else(|| ss.find_syntax_plain_text());
 
-                // Generate highlighted HTML
-                match highlighted_html_for_string(&code_content, &ss, syntax, syntax_theme) {
-                    Ok(highlighted) => {
-                        html_output.push_str(&highlighted);
+                out.push_str("<div class=\"code-block\">\n");
+                if let Some(label) = code_lang_display_name(&code_lang) {
+                    out.push_str(&format!(
+                        "<div class=\"code-block-label\">{}</div>\n",
+                        html_escape(&label)
+                    ));
+                }
+                if copy_button {
+                    out.push_str(
+                        "<button type=\"button\" class=\"code-copy-button\" onclick=\"vgetCopyCode(this)\">Copy</button>\n",
+                    );
+                }
+
+                // A plain fenced block with neither the line-number flag nor
+                // a `{..}` highlight range renders exactly as before, via
+                // syntect's single-pass highlighter.
+                if !line_numbers && code_highlight_ranges.is_empty() {
+                    match highlighted_html_for_string(&code_content, &ss, syntax, syntax_theme) {
+                        Ok(highlighted) => {
+                            out.push_str(&highlighted);
+                        }
+                        Err(_) => {
+                            // Fallback to plain code block
+                            out.push_str("<pre><code>");
+                            out.push_str(&html_escape(&code_content));
+                            out.push_str("</code></pre>\n");
+                        }
                     }
-                    Err(_) => {
-                        // Fallback to plain code block
-                        html_output.push_str("<pre><code>");
-                        html_output.push_str(&html_escape(&code_content));
-                        html_output.push_str("</code></pre>\n");
+                } else {
+                    match render_code_lines(
+                        &code_content,
+                        syntax,
+                        syntax_theme,
+                        &ss,
+                        &code_highlight_ranges,
+                        line_numbers,
+                    ) {
+                        Ok(highlighted) => out.push_str(&highlighted),
+                        Err(_) => {
+                            out.push_str("<pre><code>");
+                            out.push_str(&html_escape(&code_content));
+                            out.push_str("</code></pre>\n");
+                        }
                     }
                 }
+                out.push_str("</div>\n");
             }
             Event::Text(text) if in_code_block => {
                 code_content.push_str(&text);
             }
             Event::Start(Tag::Table(alignments)) => {
-                html_output.push_str("<table>\n");
+                out.push_str("<table>\n");
                 // Store alignments for later use (simplified - we just open the table)
                 let _ = alignments;
             }
             Event::End(TagEnd::Table) => {
-                html_output.push_str("</tbody>\n</table>\n");
+                out.push_str("</tbody>\n</table>\n");
             }
             Event::Start(Tag::TableHead) => {
                 in_table_head = true;
-                html_output.push_str("<thead>\n");
+                out.push_str("<thead>\n");
             }
             Event::End(TagEnd::TableHead) => {
                 in_table_head = false;
-                html_output.push_str("</thead>\n<tbody>\n");
+                out.push_str("</thead>\n<tbody>\n");
             }
             Event::Start(Tag::TableRow) => {
-                html_output.push_str("<tr>\n");
+                out.push_str("<tr>\n");
             }
             Event::End(TagEnd::TableRow) => {
-                html_output.push_str("</tr>\n");
+                out.push_str("</tr>\n");
             }
             Event::Start(Tag::TableCell) => {
                 if in_table_head {
-                    html_output.push_str("<th>");
+                    out.push_str("<th>");
                 } else {
-                    html_output.push_str("<td>");
+                    out.push_str("<td>");
                 }
             }
             Event::End(TagEnd::TableCell) => {
                 if in_table_head {
-                    html_output.push_str("</th>\n");
+                    out.push_str("</th>\n");
                 } else {
-                    html_output.push_str("</td>\n");
+                    out.push_str("</td>\n");
                 }
             }
             Event::Start(Tag::Heading { level, .. }) => {
-                let level_num = heading_level_to_u8(level);
-                html_output.push_str(&format!("<h{}>", level_num));
+                in_heading = true;
+                heading_level = heading_level_to_u8(level);
+                heading_html.clear();
+                heading_text.clear();
             }
-            Event::End(TagEnd::Heading(level)) => {
-                let level_num = heading_level_to_u8(level);
-                html_output.push_str(&format!("</h{}>\n", level_num));
+            Event::End(TagEnd::Heading(_)) => {
+                in_heading = false;
+                let slug = unique_slug(&heading_text, &mut slug_counts);
+                if heading_level == 1 && document_title.is_none() {
+                    document_title = Some(heading_text.clone());
+                }
+                html_output.push_str(&format!(
+                    "<h{0} id=\"{1}\">{2}</h{0}>\n",
+                    heading_level, slug, heading_html
+                ));
+                toc_entries.push(TocEntry {
+                    level: heading_level,
+                    slug,
+                    label_html: heading_html.clone(),
+                });
             }
             Event::Start(Tag::Paragraph) => {
-                html_output.push_str("<p>");
+                out.push_str("<p>");
             }
             Event::End(TagEnd::Paragraph) => {
-                html_output.push_str("</p>\n");
+                out.push_str("</p>\n");
             }
             Event::Start(Tag::List(None)) => {
-                html_output.push_str("<ul>\n");
+                out.push_str("<ul>\n");
             }
             Event::Start(Tag::List(Some(start))) => {
-                html_output.push_str(&format!("<ol start=\"{}\">\n", start));
+                out.push_str(&format!("<ol start=\"{}\">\n", start));
             }
             Event::End(TagEnd::List(ordered)) => {
                 if ordered {
-                    html_output.push_str("</ol>\n");
+                    out.push_str("</ol>\n");
                 } else {
-                    html_output.push_str("</ul>\n");
+                    out.push_str("</ul>\n");
                 }
             }
             Event::Start(Tag::Item) => {
-                html_output.push_str("<li>");
+                out.push_str("<li>");
             }
             Event::End(TagEnd::Item) => {
-                html_output.push_str("</li>\n");
+                out.push_str("</li>\n");
             }
             Event::Start(Tag::BlockQuote(_)) => {
-                html_output.push_str("<blockquote>\n");
+                out.push_str("<blockquote>\n");
             }
             Event::End(TagEnd::BlockQuote(_)) => {
-                html_output.push_str("</blockquote>\n");
+                out.push_str("</blockquote>\n");
             }
             Event::Start(Tag::Emphasis) => {
-                html_output.push_str("<em>");
+                out.push_str("<em>");
             }
             Event::End(TagEnd::Emphasis) => {
-                html_output.push_str("</em>");
+                out.push_str("</em>");
             }
             Event::Start(Tag::Strong) => {
-                html_output.push_str("<strong>");
+                out.push_str("<strong>");
             }
             Event::End(TagEnd::Strong) => {
-                html_output.push_str("</strong>");
+                out.push_str("</strong>");
             }
             Event::Start(Tag::Strikethrough) => {
-                html_output.push_str("<del>");
+                out.push_str("<del>");
             }
             Event::End(TagEnd::Strikethrough) => {
-                html_output.push_str("</del>");
+                out.push_str("</del>");
             }
             Event::Start(Tag::Link { dest_url, title, .. }) => {
-                html_output.push_str(&format!(
+                out.push_str(&format!(
                     "<a href=\"{}\" title=\"{}\">",
                     html_escape(&dest_url),
                     html_escape(&title)
                 ));
             }
             Event::End(TagEnd::Link) => {
-                html_output.push_str("</a>");
+                out.push_str("</a>");
             }
             Event::Start(Tag::Image { dest_url, title, .. }) => {
-                html_output.push_str(&format!(
+                out.push_str(&format!(
                     "<img src=\"{}\" alt=\"",
                     html_escape(&dest_url)
                 ));
@@ -213,41 +448,141 @@ fn markdown_to_html(markdown: &str, theme: &str) -> String {
                 let _ = title;
             }
             Event::End(TagEnd::Image) => {
-                html_output.push_str("\" />");
+                out.push_str("\" />");
             }
             Event::Code(code) => {
-                html_output.push_str("<code>");
-                html_output.push_str(&html_escape(&code));
-                html_output.push_str("</code>");
+                out.push_str("<code>");
+                out.push_str(&html_escape(&code));
+                out.push_str("</code>");
             }
             Event::Text(text) => {
-                html_output.push_str(&html_escape(&text));
+                out.push_str(&html_escape(&text));
             }
             Event::SoftBreak => {
-                html_output.push('\n');
+                out.push('\n');
             }
             Event::HardBreak => {
-                html_output.push_str("<br />\n");
+                out.push_str("<br />\n");
             }
             Event::Rule => {
-                html_output.push_str("<hr />\n");
+                out.push_str("<hr />\n");
             }
             Event::TaskListMarker(checked) => {
                 if checked {
-                    html_output.push_str("<input type=\"checkbox\" checked disabled /> ");
+                    out.push_str("<input type=\"checkbox\" checked disabled /> ");
                 } else {
-                    html_output.push_str("<input type=\"checkbox\" disabled /> ");
+                    out.push_str("<input type=\"checkbox\" disabled /> ");
                 }
             }
             _ => {}
         }
     }
 
-    html_output
+    let toc_html = if toc {
+        build_toc_html(&toc_entries, toc_max_depth.unwrap_or(6))
+    } else {
+        None
+    };
+
+    Ok((html_output, toc_html, toc_entries, document_title))
+}
+
+/// One heading collected while parsing, used to build the TOC and to give
+/// the heading element its `id`. Also reused to build the EPUB nav/ncx
+/// documents, which need the heading structure regardless of whether an
+/// in-document TOC was requested.
+#[derive(Clone)]
+pub(crate) struct TocEntry {
+    pub(crate) level: u8,
+    pub(crate) slug: String,
+    pub(crate) label_html: String,
+}
+
+/// Assemble a nested `<ul>`/`<li>` tree of `<a href="#slug">` links from the
+/// headings collected during parsing, dropping headings past `max_depth`.
+/// Returns `None` for an empty document or one with a single heading (e.g.
+/// just a title), since a TOC isn't useful there.
+pub(crate) fn build_toc_html(entries: &[TocEntry], max_depth: u8) -> Option<String> {
+    let entries: Vec<&TocEntry> = entries.iter().filter(|e| e.level <= max_depth).collect();
+    if entries.len() < 2 {
+        return None;
+    }
+
+    let mut toc = String::from("<nav id=\"TOC\">\n<ul>\n");
+    let mut levels = vec![entries[0].level];
+    toc.push_str(&format!(
+        "<li><a href=\"#{}\">{}</a>",
+        entries[0].slug, entries[0].label_html
+    ));
+
+    for entry in &entries[1..] {
+        let current = *levels.last().unwrap();
+        if entry.level > current {
+            toc.push_str("\n<ul>\n");
+            levels.push(entry.level);
+        } else {
+            while levels.len() > 1 && entry.level < *levels.last().unwrap() {
+                toc.push_str("</li>\n</ul>\n");
+                levels.pop();
+            }
+            toc.push_str("</li>\n");
+        }
+        toc.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a>",
+            entry.slug, entry.label_html
+        ));
+    }
+
+    toc.push_str("</li>\n");
+    for _ in 1..levels.len() {
+        toc.push_str("</ul>\n</li>\n");
+    }
+    toc.push_str("</ul>\n</nav>\n");
+
+    Some(toc)
+}
+
+/// Lowercase, strip punctuation, and collapse whitespace/underscores into
+/// single hyphens, GitHub-anchor style.
+pub(crate) fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    let mut pending_dash = false;
+
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.extend(c.to_lowercase());
+        } else if c.is_whitespace() || c == '-' || c == '_' {
+            pending_dash = true;
+        }
+        // Other punctuation is stripped entirely.
+    }
+
+    slug
+}
+
+/// Slugify `text` and disambiguate against previously seen headings by
+/// appending `-1`, `-2`, ... The counter is keyed on the base slug so it
+/// stays stable regardless of how earlier duplicates were disambiguated.
+pub(crate) fn unique_slug(text: &str, slug_counts: &mut HashMap<String, u32>) -> String {
+    let base = slugify(text);
+    let base = if base.is_empty() { "section".to_string() } else { base };
+
+    let count = slug_counts.entry(base.clone()).or_insert(0);
+    let slug = if *count == 0 {
+        base
+    } else {
+        format!("{}-{}", base, count)
+    };
+    *count += 1;
+    slug
 }
 
 /// Escape HTML special characters
-fn html_escape(text: &str) -> String {
+pub(crate) fn html_escape(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -267,6 +602,201 @@ fn heading_level_to_u8(level: HeadingLevel) -> u8 {
     }
 }
 
+/// Map a fenced code block's language token to a human-readable display name
+/// for the code block header bar, e.g. `cpp` -> "C++". Falls back to
+/// uppercasing the token for anything not in the table, and returns `None`
+/// for an indented block (no token at all), which gets no header bar.
+pub(crate) fn code_lang_display_name(token: &str) -> Option<String> {
+    if token.is_empty() {
+        return None;
+    }
+
+    const ALIASES: &[(&str, &str)] = &[
+        ("bash", "Bash"),
+        ("sh", "Shell"),
+        ("shell", "Shell"),
+        ("zsh", "Zsh"),
+        ("js", "JavaScript"),
+        ("javascript", "JavaScript"),
+        ("jsx", "JSX"),
+        ("ts", "TypeScript"),
+        ("typescript", "TypeScript"),
+        ("tsx", "TSX"),
+        ("py", "Python"),
+        ("python", "Python"),
+        ("rb", "Ruby"),
+        ("ruby", "Ruby"),
+        ("go", "Go"),
+        ("golang", "Go"),
+        ("rs", "Rust"),
+        ("rust", "Rust"),
+        ("cpp", "C++"),
+        ("c++", "C++"),
+        ("cs", "C#"),
+        ("csharp", "C#"),
+        ("c", "C"),
+        ("java", "Java"),
+        ("php", "PHP"),
+        ("html", "HTML"),
+        ("css", "CSS"),
+        ("scss", "SCSS"),
+        ("json", "JSON"),
+        ("yaml", "YAML"),
+        ("yml", "YAML"),
+        ("toml", "TOML"),
+        ("xml", "XML"),
+        ("sql", "SQL"),
+        ("kt", "Kotlin"),
+        ("kotlin", "Kotlin"),
+        ("swift", "Swift"),
+        ("dockerfile", "Dockerfile"),
+        ("md", "Markdown"),
+        ("markdown", "Markdown"),
+        ("diff", "Diff"),
+        ("makefile", "Makefile"),
+    ];
+
+    let lower = token.to_lowercase();
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| token.to_uppercase());
+
+    Some(name)
+}
+
+/// Render a fenced code block to the same `.code-block`/`.code-block-label`
+/// markup the Markdown front end emits, so the AsciiDoc front end
+/// ([`crate::asciidoc`]) gets identical code-block styling without
+/// duplicating the syntect setup.
+pub(crate) fn render_fenced_code_block(lang: &str, code: &str, theme: &str) -> String {
+    let ss = SyntaxSet::load_defaults_newlines();
+    let ts = ThemeSet::load_defaults();
+    let syntax_theme = if Theme::parse(theme).is_dark() {
+        &ts.themes["base16-ocean.dark"]
+    } else {
+        &ts.themes["InspiredGitHub"]
+    };
+    let syntax = if !lang.is_empty() { ss.find_syntax_by_token(lang) } else { None }
+        .unwrap_or_else(|| ss.find_syntax_plain_text());
+
+    let mut out = String::from("<div class=\"code-block\">\n");
+    if let Some(label) = code_lang_display_name(lang) {
+        out.push_str(&format!(
+            "<div class=\"code-block-label\">{}</div>\n",
+            html_escape(&label)
+        ));
+    }
+    match highlighted_html_for_string(code, &ss, syntax, syntax_theme) {
+        Ok(highlighted) => out.push_str(&highlighted),
+        Err(_) => {
+            out.push_str("<pre><code>");
+            out.push_str(&html_escape(code));
+            out.push_str("</code></pre>\n");
+        }
+    }
+    out.push_str("</div>\n");
+    out
+}
+
+/// Split a fenced code block's info string into its language token and an
+/// optional `{1,4-6}` line-highlight suffix, e.g. `rust {2,5-7}` for
+/// tutorials and diffs. Ranges are parsed as inclusive, clamping/normalization
+/// against the actual line count happens in [`render_code_lines`] once the
+/// content is known. The language token doubles as the [`code_lang_display_name`]
+/// badge and the [`SyntaxSet`] lookup key, so both the gutter/highlight
+/// feature and the language label share this one parse.
+fn parse_fence_info(info: &str) -> (String, Vec<(usize, usize)>) {
+    let info = info.trim();
+    let Some(brace_start) = info.find('{') else {
+        return (info.to_string(), Vec::new());
+    };
+    let Some(brace_end) = info[brace_start..].find('}') else {
+        return (info.to_string(), Vec::new());
+    };
+
+    let lang = info[..brace_start].trim().to_string();
+    let spec = &info[brace_start + 1..brace_start + brace_end];
+
+    let ranges = spec
+        .split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            if part.is_empty() {
+                return None;
+            }
+            match part.split_once('-') {
+                Some((start, end)) => {
+                    let start: usize = start.trim().parse().ok()?;
+                    let end: usize = end.trim().parse().ok()?;
+                    Some((start.min(end), start.max(end)))
+                }
+                None => {
+                    let line: usize = part.parse().ok()?;
+                    Some((line, line))
+                }
+            }
+        })
+        .collect();
+
+    (lang, ranges)
+}
+
+/// Render a code block line-by-line (needed for both the line-number gutter
+/// and the `{..}` highlighted-line ranges), matching the markup structure
+/// `highlighted_html_for_string` would otherwise produce (a `<pre>` with the
+/// theme's background color, wrapping a `<code>`).
+fn render_code_lines(
+    code: &str,
+    syntax: &SyntaxReference,
+    syntax_theme: &SyntaxTheme,
+    ss: &SyntaxSet,
+    highlight_ranges: &[(usize, usize)],
+    line_numbers: bool,
+) -> Result<String, String> {
+    let total_lines = code.lines().count().max(1);
+    // Clamp to the actual line count and drop now-empty ranges; overlap
+    // doesn't need merging since membership is checked with `any`.
+    let ranges: Vec<(usize, usize)> = highlight_ranges
+        .iter()
+        .map(|&(start, end)| (start.max(1), end.min(total_lines)))
+        .filter(|(start, end)| start <= end)
+        .collect();
+
+    let bg = syntax_theme
+        .settings
+        .background
+        .unwrap_or(syntect::highlighting::Color { r: 255, g: 255, b: 255, a: 255 });
+
+    let mut html = format!(
+        "<pre class=\"{}\" style=\"background-color:#{:02x}{:02x}{:02x};\">\n<code>\n",
+        if line_numbers { "with-line-numbers" } else { "" },
+        bg.r,
+        bg.g,
+        bg.b
+    );
+
+    let mut highlighter = HighlightLines::new(syntax, syntax_theme);
+    for (index, line) in LinesWithEndings::from(code).enumerate() {
+        let line_no = index + 1;
+        let styled = highlighter
+            .highlight_line(line, ss)
+            .map_err(|e| format!("Syntax highlighting error: {}", e))?;
+        let line_html = styled_line_to_highlighted_html(&styled, IncludeBackground::No)
+            .map_err(|e| format!("Syntax highlighting error: {}", e))?;
+
+        let mut class = String::from("code-line");
+        if ranges.iter().any(|&(start, end)| line_no >= start && line_no <= end) {
+            class.push_str(" highlighted-line");
+        }
+        html.push_str(&format!("<span class=\"{}\">{}</span>\n", class, line_html));
+    }
+
+    html.push_str("</code></pre>\n");
+    Ok(html)
+}
+
 /// Generate @font-face CSS rules with embedded base64 fonts
 fn generate_font_css() -> String {
     format!(
@@ -332,12 +862,34 @@ fn generate_font_css() -> String {
     )
 }
 
-/// Generate full HTML document with CSS styling
-fn generate_styled_html(content: &str, theme: &str) -> String {
-    let font_css = generate_font_css();
+/// Generate full HTML document with CSS styling. `embed_fonts` inlines the
+/// bundled Inter/JetBrains Mono faces as base64 `@font-face` rules so the
+/// document renders identically everywhere, even offline; disabling it
+/// falls back to the system font stack declared on `body` and produces a
+/// smaller file. `copy_button` emits the tiny click handler backing the
+/// copy-to-clipboard buttons rendered onto code blocks.
+fn generate_styled_html(
+    content: &str,
+    theme: &str,
+    toc_html: Option<&str>,
+    custom_css_path: Option<&str>,
+    embed_fonts: bool,
+    copy_button: bool,
+) -> Result<String, String> {
+    let font_css = if embed_fonts { generate_font_css() } else { String::new() };
     let theme_css = get_theme_css(theme);
+    let toc_html = toc_html.unwrap_or("");
+    let copy_script = if copy_button { COPY_BUTTON_SCRIPT } else { "" };
+
+    // Appended after the built-in theme CSS so it cascades as an override
+    // rather than a full replacement.
+    let custom_css = match custom_css_path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read custom CSS file '{}': {}", path, e))?,
+        None => String::new(),
+    };
 
-    format!(
+    Ok(format!(
         r#"<!DOCTYPE html>
 <html>
 <head>
@@ -345,27 +897,81 @@ fn generate_styled_html(content: &str, theme: &str) -> String {
     <style>
 {font_css}
 {theme_css}
+{custom_css}
     </style>
 </head>
 <body>
     <article class="markdown-body">
-{content}
+{toc_html}{content}
     </article>
+{copy_script}
 </body>
 </html>"#
+    ))
+}
+
+/// Click handler backing `.code-copy-button`: copies the sibling `<pre>`'s
+/// text and flips the button's label to "Copied" for a moment as feedback.
+const COPY_BUTTON_SCRIPT: &str = r#"<script>
+function vgetCopyCode(btn) {
+    var block = btn.closest(".code-block");
+    var pre = block ? block.querySelector("pre") : null;
+    var text = pre ? pre.innerText : "";
+    navigator.clipboard.writeText(text).then(function () {
+        var original = btn.textContent;
+        btn.textContent = "Copied";
+        setTimeout(function () {
+            btn.textContent = original;
+        }, 1500);
+    });
+}
+</script>"#;
+
+/// Chrome's `header_template` showing the document title, using its special
+/// `title` class (filled in by Chrome itself, but we inline the known value
+/// so it renders even where Chrome's substitution is unavailable).
+fn header_template(title: &str) -> String {
+    format!(
+        r#"<div style="width:100%; font-size:9px; color:#888; text-align:center; padding:0 0.5in;"><span class="title">{}</span></div>"#,
+        html_escape(title)
+    )
+}
+
+/// Chrome's `footer_template` with "Page X of Y" (via the `pageNumber` /
+/// `totalPages` classes) placed at `position` (`left`/`center`/`right`),
+/// with optional custom `footer_text` alongside it.
+fn footer_template(footer_text: Option<&str>, position: &str) -> String {
+    let justify = match position {
+        "left" => "flex-start",
+        "right" => "flex-end",
+        _ => "center",
+    };
+    let text_span = footer_text
+        .map(|t| format!("<span>{}</span>&nbsp;&nbsp;", html_escape(t)))
+        .unwrap_or_default();
+
+    format!(
+        r#"<div style="display:flex; justify-content:{justify}; align-items:center; width:100%; font-size:9px; color:#888; padding:0 0.5in;">{text_span}<span>Page <span class="pageNumber"></span> of <span class="totalPages"></span></span></div>"#
     )
 }
 
-/// Get CSS based on theme
+/// Get CSS based on theme name; see [`Theme::parse`] for recognized names.
 fn get_theme_css(theme: &str) -> &'static str {
-    match theme {
-        "dark" => DARK_THEME_CSS,
-        _ => LIGHT_THEME_CSS,
-    }
+    Theme::parse(theme).css()
 }
 
-/// Convert HTML to PDF using headless Chrome
-fn html_to_pdf(html: &str, output_path: &str, page_size: &str) -> Result<(), String> {
+/// Convert HTML to PDF using headless Chrome, optionally with a running
+/// header (document title) and footer (page numbers / custom text).
+fn html_to_pdf(
+    html: &str,
+    output_path: &str,
+    page_size: &str,
+    title: &str,
+    header_footer: bool,
+    footer_text: Option<&str>,
+    page_number_position: &str,
+    margin_in: Option<f64>,
+) -> Result<(), String> {
     // Write HTML to a temp file (more reliable than data URLs for large content)
     let temp_dir = std::env::temp_dir();
     let temp_html_path = temp_dir.join(format!("md2pdf_{}.html", std::process::id()));
@@ -403,18 +1009,30 @@ fn html_to_pdf(html: &str, output_path: &str, page_size: &str) -> Result<(), Str
         _ => (8.27, 11.69), // A4 default
     };
 
+    // Chrome hides page content behind the header/footer bands unless the
+    // top/bottom margins leave room for them. An explicit `margin_in`
+    // overrides this default for all four edges.
+    let (margin_top, margin_bottom) = match margin_in {
+        Some(m) => (m, m),
+        None if header_footer => (0.75, 0.75),
+        None => (0.5, 0.5),
+    };
+    let (margin_left, margin_right) = (margin_in.unwrap_or(0.5), margin_in.unwrap_or(0.5));
+
     // Generate PDF with custom options
     let options = PrintToPdfOptions {
         landscape: Some(false),
-        display_header_footer: Some(false),
+        display_header_footer: Some(header_footer),
+        header_template: header_footer.then(|| header_template(title)),
+        footer_template: header_footer.then(|| footer_template(footer_text, page_number_position)),
         print_background: Some(true),
         scale: Some(1.0),
         paper_width: Some(paper_width),
         paper_height: Some(paper_height),
-        margin_top: Some(0.5),
-        margin_bottom: Some(0.5),
-        margin_left: Some(0.5),
-        margin_right: Some(0.5),
+        margin_top: Some(margin_top),
+        margin_bottom: Some(margin_bottom),
+        margin_left: Some(margin_left),
+        margin_right: Some(margin_right),
         prefer_css_page_size: Some(false),
         ..Default::default()
     };
@@ -438,8 +1056,2138 @@ fn html_to_pdf(html: &str, output_path: &str, page_size: &str) -> Result<(), Str
     Ok(())
 }
 
-// Light theme CSS - Professional document styling
-const LIGHT_THEME_CSS: &str = r#"
+/// Package the parsed document as an EPUB3 container: a single XHTML
+/// content file styled with the theme CSS, its fonts as real files under
+/// `fonts/` (rather than base64, so readers don't re-decode them on every
+/// page), a nav document built from the heading structure, and a
+/// `toc.ncx` fallback for EPUB2 readers. `title` (the document's first `h1`,
+/// or the input filename, same fallback as the PDF path) is used for both
+/// the content document's `<title>` and the OPF's `<dc:title>`.
+fn write_epub(
+    content: &str,
+    theme: &str,
+    toc_html: Option<&str>,
+    custom_css_path: Option<&str>,
+    toc_entries: &[TocEntry],
+    output_path: &str,
+    title: &str,
+) -> Result<(), String> {
+    if let Some(parent) = Path::new(output_path).parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    let theme_css = get_theme_css(theme);
+    let font_css = generate_epub_font_css();
+    let custom_css = match custom_css_path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read custom CSS file '{}': {}", path, e))?,
+        None => String::new(),
+    };
+    let toc_html = toc_html.unwrap_or("");
+    let title = html_escape(title);
+
+    let content_xhtml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head>
+    <meta charset="UTF-8"/>
+    <title>{title}</title>
+    <style>
+{font_css}
+{theme_css}
+{custom_css}
+    </style>
+</head>
+<body>
+    <article class="markdown-body">
+{toc_html}{content}
+    </article>
+</body>
+</html>"#
+    );
+
+    let nav_xhtml = generate_nav_xhtml(toc_entries);
+    let toc_ncx = generate_toc_ncx(toc_entries);
+    let content_opf = generate_content_opf(&title);
+
+    let file = fs::File::create(output_path).map_err(|e| format!("Failed to create EPUB file: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+
+    // Must be the first entry, stored uncompressed, so readers can
+    // identify the file as EPUB without inflating anything.
+    let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file("mimetype", stored)
+        .map_err(|e| format!("Failed to write EPUB mimetype entry: {}", e))?;
+    zip.write_all(b"application/epub+zip")
+        .map_err(|e| format!("Failed to write EPUB mimetype entry: {}", e))?;
+
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    write_zip_str(&mut zip, deflated, "META-INF/container.xml", CONTAINER_XML)?;
+    write_zip_str(&mut zip, deflated, "OEBPS/content.opf", &content_opf)?;
+    write_zip_str(&mut zip, deflated, "OEBPS/nav.xhtml", &nav_xhtml)?;
+    write_zip_str(&mut zip, deflated, "OEBPS/toc.ncx", &toc_ncx)?;
+    write_zip_str(&mut zip, deflated, "OEBPS/content.xhtml", &content_xhtml)?;
+
+    write_zip_bytes(&mut zip, deflated, "OEBPS/fonts/Inter-Regular.woff2", INTER_REGULAR)?;
+    write_zip_bytes(&mut zip, deflated, "OEBPS/fonts/Inter-Medium.woff2", INTER_MEDIUM)?;
+    write_zip_bytes(&mut zip, deflated, "OEBPS/fonts/Inter-SemiBold.woff2", INTER_SEMIBOLD)?;
+    write_zip_bytes(&mut zip, deflated, "OEBPS/fonts/Inter-Bold.woff2", INTER_BOLD)?;
+    write_zip_bytes(&mut zip, deflated, "OEBPS/fonts/Inter-ExtraBold.woff2", INTER_EXTRABOLD)?;
+    write_zip_bytes(
+        &mut zip,
+        deflated,
+        "OEBPS/fonts/JetBrainsMono-Regular.woff2",
+        JETBRAINS_MONO_REGULAR,
+    )?;
+    write_zip_bytes(
+        &mut zip,
+        deflated,
+        "OEBPS/fonts/JetBrainsMono-Medium.woff2",
+        JETBRAINS_MONO_MEDIUM,
+    )?;
+
+    zip.finish().map_err(|e| format!("Failed to finalize EPUB archive: {}", e))?;
+
+    Ok(())
+}
+
+fn write_zip_str(
+    zip: &mut ZipWriter<fs::File>,
+    options: FileOptions,
+    name: &str,
+    contents: &str,
+) -> Result<(), String> {
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to write EPUB entry '{}': {}", name, e))?;
+    zip.write_all(contents.as_bytes())
+        .map_err(|e| format!("Failed to write EPUB entry '{}': {}", name, e))
+}
+
+fn write_zip_bytes(
+    zip: &mut ZipWriter<fs::File>,
+    options: FileOptions,
+    name: &str,
+    contents: &[u8],
+) -> Result<(), String> {
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to write EPUB entry '{}': {}", name, e))?;
+    zip.write_all(contents)
+        .map_err(|e| format!("Failed to write EPUB entry '{}': {}", name, e))
+}
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+    <rootfiles>
+        <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+    </rootfiles>
+</container>"#;
+
+/// @font-face rules pointing at the real font files packaged under
+/// `fonts/`, rather than the base64 data URIs used for the PDF/HTML
+/// output (a reflowable EPUB benefits from the browser caching the font
+/// file once instead of re-decoding it on every page).
+fn generate_epub_font_css() -> String {
+    r#"
+/* Embedded Fonts */
+@font-face {
+    font-family: 'Inter';
+    font-style: normal;
+    font-weight: 400;
+    src: url(fonts/Inter-Regular.woff2) format('woff2');
+}
+@font-face {
+    font-family: 'Inter';
+    font-style: normal;
+    font-weight: 500;
+    src: url(fonts/Inter-Medium.woff2) format('woff2');
+}
+@font-face {
+    font-family: 'Inter';
+    font-style: normal;
+    font-weight: 600;
+    src: url(fonts/Inter-SemiBold.woff2) format('woff2');
+}
+@font-face {
+    font-family: 'Inter';
+    font-style: normal;
+    font-weight: 700;
+    src: url(fonts/Inter-Bold.woff2) format('woff2');
+}
+@font-face {
+    font-family: 'Inter';
+    font-style: normal;
+    font-weight: 800;
+    src: url(fonts/Inter-ExtraBold.woff2) format('woff2');
+}
+@font-face {
+    font-family: 'JetBrains Mono';
+    font-style: normal;
+    font-weight: 400;
+    src: url(fonts/JetBrainsMono-Regular.woff2) format('woff2');
+}
+@font-face {
+    font-family: 'JetBrains Mono';
+    font-style: normal;
+    font-weight: 500;
+    src: url(fonts/JetBrainsMono-Medium.woff2) format('woff2');
+}
+"#
+    .to_string()
+}
+
+fn generate_content_opf(title: &str) -> String {
+    let uuid = uuid::Uuid::new_v4();
+    let title = html_escape(title);
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:identifier id="book-id">urn:uuid:{uuid}</dc:identifier>
+        <dc:title>{title}</dc:title>
+        <dc:language>en</dc:language>
+    </metadata>
+    <manifest>
+        <item id="content" href="content.xhtml" media-type="application/xhtml+xml"/>
+        <item id="nav" href="nav.xhtml" properties="nav" media-type="application/xhtml+xml"/>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="font-inter-regular" href="fonts/Inter-Regular.woff2" media-type="font/woff2"/>
+        <item id="font-inter-medium" href="fonts/Inter-Medium.woff2" media-type="font/woff2"/>
+        <item id="font-inter-semibold" href="fonts/Inter-SemiBold.woff2" media-type="font/woff2"/>
+        <item id="font-inter-bold" href="fonts/Inter-Bold.woff2" media-type="font/woff2"/>
+        <item id="font-inter-extrabold" href="fonts/Inter-ExtraBold.woff2" media-type="font/woff2"/>
+        <item id="font-mono-regular" href="fonts/JetBrainsMono-Regular.woff2" media-type="font/woff2"/>
+        <item id="font-mono-medium" href="fonts/JetBrainsMono-Medium.woff2" media-type="font/woff2"/>
+    </manifest>
+    <spine toc="ncx">
+        <itemref idref="content"/>
+    </spine>
+</package>"#
+    )
+}
+
+/// EPUB3 nav document (`epub:type="toc"`), built from the same heading
+/// structure as the in-document TOC.
+fn generate_nav_xhtml(entries: &[TocEntry]) -> String {
+    let links = entries
+        .iter()
+        .map(|e| format!("<li><a href=\"content.xhtml#{}\">{}</a></li>\n", e.slug, e.label_html))
+        .collect::<String>();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+<head>
+    <meta charset="UTF-8"/>
+    <title>Table of Contents</title>
+</head>
+<body>
+    <nav epub:type="toc" id="toc">
+        <ol>
+{links}        </ol>
+    </nav>
+</body>
+</html>"#
+    )
+}
+
+/// EPUB2 `toc.ncx` fallback for readers that don't support the EPUB3 nav
+/// document.
+fn generate_toc_ncx(entries: &[TocEntry]) -> String {
+    let nav_points = entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            format!(
+                "<navPoint id=\"navPoint-{0}\" playOrder=\"{0}\">\n<navLabel><text>{1}</text></navLabel>\n<content src=\"content.xhtml#{2}\"/>\n</navPoint>\n",
+                i + 1,
+                e.label_html,
+                e.slug
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <head></head>
+    <docTitle><text>Document</text></docTitle>
+    <navMap>
+{nav_points}    </navMap>
+</ncx>"#
+    )
+}
+
+// Light theme CSS - Professional document styling
+const LIGHT_THEME_CSS: &str = r#"
+@page {
+    margin: 0;
+    size: auto;
+}
+
+*, *::before, *::after {
+    box-sizing: border-box;
+}
+
+html {
+    font-size: 15px;
+    -webkit-print-color-adjust: exact;
+    print-color-adjust: exact;
+    text-rendering: optimizeLegibility;
+    -webkit-font-smoothing: antialiased;
+    -moz-osx-font-smoothing: grayscale;
+}
+
+body {
+    font-family: "Inter", -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial, sans-serif,
+        "PingFang SC", "Hiragino Sans GB", "Microsoft YaHei",
+        "Hiragino Kaku Gothic Pro", "Yu Gothic",
+        "Apple SD Gothic Neo", "Malgun Gothic",
+        "Apple Color Emoji", "Segoe UI Emoji";
+    font-size: 1rem;
+    font-weight: 400;
+    line-height: 1.7;
+    color: #1a1a2e;
+    background-color: #ffffff;
+    margin: 0;
+    padding: 0;
+    word-wrap: break-word;
+    font-feature-settings: "kern" 1, "liga" 1, "calt" 1;
+}
+
+.markdown-body {
+    max-width: 100%;
+    margin: 0 auto;
+    padding: 56px 64px;
+}
+
+/* ==================== Typography ==================== */
+
+/* Headings */
+h1, h2, h3, h4, h5, h6 {
+    font-weight: 700;
+    line-height: 1.35;
+    color: #0f0f23;
+    margin-top: 2em;
+    margin-bottom: 0.8em;
+    letter-spacing: -0.02em;
+    page-break-after: avoid;
+    page-break-inside: avoid;
+}
+
+h1:first-child, h2:first-child, h3:first-child,
+h4:first-child, h5:first-child, h6:first-child {
+    margin-top: 0;
+}
+
+h1 {
+    font-size: 2.4rem;
+    font-weight: 800;
+    letter-spacing: -0.03em;
+    color: #0a0a1a;
+    padding-bottom: 0.5em;
+    margin-bottom: 1.2em;
+    border-bottom: 3px solid #e8e8f0;
+}
+
+h2 {
+    font-size: 1.8rem;
+    font-weight: 700;
+    color: #16163a;
+    padding-bottom: 0.4em;
+    margin-bottom: 1em;
+    border-bottom: 2px solid #ececf4;
+}
+
+h3 {
+    font-size: 1.4rem;
+    font-weight: 600;
+    color: #1f1f4a;
+}
+
+h4 {
+    font-size: 1.15rem;
+    font-weight: 600;
+    color: #2a2a5a;
+}
+
+h5 {
+    font-size: 1rem;
+    font-weight: 600;
+    color: #3a3a6a;
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+}
+
+h6 {
+    font-size: 0.9rem;
+    font-weight: 600;
+    color: #5a5a8a;
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+}
+
+/* Paragraphs */
+p {
+    margin-top: 0;
+    margin-bottom: 1.35em;
+    line-height: 1.75;
+}
+
+/* Links */
+a {
+    color: #2563eb;
+    text-decoration: none;
+    border-bottom: 1px solid transparent;
+    transition: border-color 0.15s ease;
+}
+
+a:hover {
+    border-bottom-color: #2563eb;
+}
+
+/* ==================== Code ==================== */
+
+/* Inline code */
+code {
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.88em;
+    font-weight: 500;
+    padding: 0.2em 0.45em;
+    background: linear-gradient(135deg, #f8f9fc 0%, #f1f3f8 100%);
+    border-radius: 5px;
+    color: #c41d7f;
+    border: 1px solid #e4e7ee;
+    white-space: nowrap;
+}
+
+/* Wraps a highlighted block plus its optional language-label header bar so
+   the two never separate across a page break. */
+.code-block {
+    position: relative;
+    margin-bottom: 1.6em;
+    page-break-inside: avoid;
+}
+
+.code-block-label {
+    display: inline-block;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.72rem;
+    font-weight: 600;
+    letter-spacing: 0.04em;
+    text-transform: uppercase;
+    color: #6366f1;
+    background: #eef0fb;
+    padding: 0.3em 0.9em;
+    border: 1px solid #e2e6ee;
+    border-bottom: none;
+    border-radius: 8px 8px 0 0;
+    margin-bottom: -1px;
+}
+
+.code-block-label + pre {
+    border-top-left-radius: 0;
+}
+
+.code-copy-button {
+    position: absolute;
+    top: 0.6em;
+    right: 0.6em;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.7rem;
+    font-weight: 600;
+    color: #6366f1;
+    background: #eef0fb;
+    border: 1px solid #e2e6ee;
+    border-radius: 6px;
+    padding: 0.25em 0.7em;
+    cursor: pointer;
+}
+
+.code-copy-button:hover {
+    background: #e2e6ee;
+}
+
+/* Code blocks - syntect generates pre with inline styles */
+pre {
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.88rem;
+    line-height: 1.65;
+    padding: 1.3em 1.5em;
+    overflow-x: auto;
+    background: linear-gradient(180deg, #fafbfd 0%, #f5f7fa 100%) !important;
+    border-radius: 10px;
+    border: 1px solid #e2e6ee;
+    margin-top: 0;
+    margin-bottom: 0;
+    box-shadow: 0 2px 8px rgba(0, 0, 0, 0.04);
+}
+
+pre code {
+    font-size: inherit;
+    font-weight: 400;
+    padding: 0;
+    background: transparent !important;
+    border: none;
+    border-radius: 0;
+    color: inherit;
+    white-space: pre;
+}
+
+.code-line {
+    display: block;
+}
+
+/* Opt-in gutter: a CSS counter rather than literal numbers in the markup,
+   so selecting/copying code never picks up the line numbers. */
+pre.with-line-numbers {
+    counter-reset: line;
+}
+
+pre.with-line-numbers .code-line {
+    counter-increment: line;
+    padding-left: 3.5em;
+    position: relative;
+}
+
+pre.with-line-numbers .code-line::before {
+    content: counter(line);
+    position: absolute;
+    left: 0;
+    width: 2.8em;
+    text-align: right;
+    color: #9aa4b8;
+    user-select: none;
+}
+
+.highlighted-line {
+    background: rgba(99, 102, 241, 0.12);
+    margin: 0 -1.5em;
+    padding-left: 1.5em;
+    padding-right: 1.5em;
+}
+
+pre.with-line-numbers .highlighted-line {
+    padding-left: calc(3.5em + 1.5em);
+}
+
+/* ==================== Blockquotes ==================== */
+
+blockquote {
+    margin: 0 0 1.5em 0;
+    padding: 1em 1.5em;
+    color: #4a5568;
+    border-left: 4px solid #6366f1;
+    background: linear-gradient(135deg, #f8f9ff 0%, #f3f4fc 100%);
+    border-radius: 0 8px 8px 0;
+    font-style: italic;
+}
+
+blockquote p {
+    margin-bottom: 0.6em;
+}
+
+blockquote p:last-child {
+    margin-bottom: 0;
+}
+
+blockquote blockquote {
+    margin-top: 0.8em;
+    border-left-color: #a5b4fc;
+}
+
+blockquote code {
+    font-style: normal;
+}
+
+/* AsciiDoc admonitions (NOTE/TIP/IMPORTANT/WARNING/CAUTION) render as a
+   blockquote with a bold, non-italic label prefix. */
+.admonition-label {
+    font-style: normal;
+    font-weight: 700;
+    text-transform: uppercase;
+    letter-spacing: 0.03em;
+    margin-right: 0.4em;
+}
+
+/* ==================== Lists ==================== */
+
+ul, ol {
+    margin-top: 0;
+    margin-bottom: 1.4em;
+    padding-left: 1.8em;
+}
+
+ul ul, ol ol, ul ol, ol ul {
+    margin-bottom: 0;
+    margin-top: 0.4em;
+}
+
+li {
+    margin-bottom: 0.45em;
+    line-height: 1.7;
+}
+
+li > p {
+    margin-bottom: 0.6em;
+}
+
+li > p:last-child {
+    margin-bottom: 0;
+}
+
+/* Custom bullet styling */
+ul {
+    list-style: none;
+}
+
+ul > li {
+    position: relative;
+    padding-left: 0.2em;
+}
+
+ul > li::before {
+    content: "";
+    position: absolute;
+    left: -1.3em;
+    top: 0.65em;
+    width: 6px;
+    height: 6px;
+    background-color: #6366f1;
+    border-radius: 50%;
+}
+
+ul ul > li::before {
+    background-color: transparent;
+    border: 1.5px solid #6366f1;
+}
+
+ul ul ul > li::before {
+    background-color: #a5b4fc;
+    border: none;
+    width: 5px;
+    height: 5px;
+}
+
+ol {
+    list-style: none;
+    counter-reset: ol-counter;
+}
+
+ol > li {
+    position: relative;
+    padding-left: 0.3em;
+    counter-increment: ol-counter;
+}
+
+ol > li::before {
+    content: counter(ol-counter) ".";
+    position: absolute;
+    left: -1.8em;
+    top: 0;
+    font-weight: 600;
+    font-size: 0.9em;
+    color: #6366f1;
+    min-width: 1.5em;
+    text-align: right;
+}
+
+/* Task lists */
+li input[type="checkbox"] {
+    margin-right: 0.6em;
+    margin-left: -0.2em;
+    vertical-align: middle;
+    position: relative;
+    top: -1px;
+    width: 16px;
+    height: 16px;
+    accent-color: #6366f1;
+}
+
+/* ==================== Table of Contents ==================== */
+
+nav#TOC {
+    margin: 0 0 2.5em;
+    padding: 1.4em 1.8em;
+    background: linear-gradient(135deg, #f8f9fc 0%, #f1f3f8 100%);
+    border: 1px solid #e4e7ee;
+    border-radius: 10px;
+    page-break-inside: avoid;
+}
+
+nav#TOC ul {
+    list-style: none;
+    margin: 0;
+    padding-left: 1.3em;
+}
+
+nav#TOC > ul {
+    padding-left: 0;
+}
+
+nav#TOC li {
+    margin-bottom: 0.4em;
+    line-height: 1.5;
+}
+
+nav#TOC li::before {
+    display: none;
+}
+
+nav#TOC a {
+    color: #1a1a2e;
+    border-bottom: none;
+}
+
+nav#TOC a:hover {
+    color: #2563eb;
+}
+
+/* ==================== Tables ==================== */
+
+table {
+    border-spacing: 0;
+    border-collapse: separate;
+    border-radius: 10px;
+    margin-top: 0;
+    margin-bottom: 1.8em;
+    width: 100%;
+    overflow: hidden;
+    box-shadow: 0 2px 12px rgba(0, 0, 0, 0.06);
+    page-break-inside: avoid;
+}
+
+thead {
+    display: table-header-group;
+}
+
+tbody {
+    display: table-row-group;
+}
+
+th, td {
+    padding: 0.85em 1.1em;
+    text-align: left;
+    border-bottom: 1px solid #e8ebf0;
+}
+
+th {
+    font-weight: 600;
+    font-size: 0.9em;
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+    color: #4a5080;
+    background: linear-gradient(180deg, #f8f9fc 0%, #f1f3f8 100%);
+    border-bottom: 2px solid #dde0e8;
+}
+
+td {
+    color: #2d3748;
+}
+
+tr:last-child td {
+    border-bottom: none;
+}
+
+tbody tr:nth-child(even) {
+    background-color: #fafbfd;
+}
+
+tbody tr:hover {
+    background-color: #f5f6fa;
+}
+
+/* ==================== Other Elements ==================== */
+
+/* Horizontal rule */
+hr {
+    height: 0;
+    padding: 0;
+    margin: 2.5em 0;
+    border: 0;
+    border-top: 2px solid #e8ebf0;
+    background: transparent;
+}
+
+/* Images */
+img {
+    max-width: 100%;
+    height: auto;
+    display: block;
+    margin: 1.5em auto;
+    border-radius: 8px;
+    box-shadow: 0 4px 16px rgba(0, 0, 0, 0.08);
+}
+
+/* Strikethrough */
+del {
+    color: #718096;
+    text-decoration: line-through;
+    text-decoration-color: #cbd5e0;
+}
+
+/* Strong and emphasis */
+strong {
+    font-weight: 650;
+    color: #0f0f23;
+}
+
+em {
+    font-style: italic;
+    color: #2d3748;
+}
+
+/* Definition lists */
+dt {
+    font-weight: 600;
+    margin-top: 1.2em;
+    color: #1a1a2e;
+}
+
+dd {
+    margin-left: 1.8em;
+    margin-bottom: 0.6em;
+    color: #4a5568;
+}
+
+/* Footnotes */
+.footnote-definition {
+    font-size: 0.88rem;
+    margin-top: 2.5em;
+    padding-top: 1.2em;
+    border-top: 2px solid #e8ebf0;
+    color: #4a5568;
+}
+
+/* Keyboard shortcut styling */
+kbd {
+    font-family: inherit;
+    font-size: 0.85em;
+    padding: 0.15em 0.4em;
+    background: linear-gradient(180deg, #fff 0%, #f5f5f5 100%);
+    border: 1px solid #d1d5db;
+    border-radius: 4px;
+    box-shadow: 0 1px 2px rgba(0,0,0,0.08), inset 0 -1px 0 rgba(0,0,0,0.1);
+}
+
+/* ==================== Print Optimizations ==================== */
+
+@media print {
+    html {
+        font-size: 14px;
+    }
+
+    body {
+        background: white;
+    }
+
+    .markdown-body {
+        padding: 40px 48px;
+    }
+
+    .code-block, blockquote, table, img, h1, h2, h3, h4, h5, h6 {
+        page-break-inside: avoid;
+    }
+
+    h1, h2, h3, h4, h5, h6 {
+        page-break-after: avoid;
+    }
+
+    p, li {
+        orphans: 3;
+        widows: 3;
+    }
+
+    a {
+        color: #2563eb;
+    }
+
+    a[href^="http"]::after {
+        content: " (" attr(href) ")";
+        font-size: 0.8em;
+        color: #718096;
+        word-break: break-all;
+    }
+
+    table {
+        box-shadow: none;
+        border: 1px solid #d1d5db;
+    }
+
+    img {
+        box-shadow: none;
+        border: 1px solid #e8ebf0;
+    }
+
+    pre {
+        box-shadow: none;
+        border: 1px solid #d1d5db;
+    }
+}
+"#;
+
+// Dark theme CSS - Professional dark document styling
+const DARK_THEME_CSS: &str = r#"
+@page {
+    margin: 0;
+    size: auto;
+}
+
+*, *::before, *::after {
+    box-sizing: border-box;
+}
+
+html {
+    font-size: 15px;
+    -webkit-print-color-adjust: exact;
+    print-color-adjust: exact;
+    text-rendering: optimizeLegibility;
+    -webkit-font-smoothing: antialiased;
+    -moz-osx-font-smoothing: grayscale;
+}
+
+body {
+    font-family: "Inter", -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial, sans-serif,
+        "PingFang SC", "Hiragino Sans GB", "Microsoft YaHei",
+        "Hiragino Kaku Gothic Pro", "Yu Gothic",
+        "Apple SD Gothic Neo", "Malgun Gothic",
+        "Apple Color Emoji", "Segoe UI Emoji";
+    font-size: 1rem;
+    font-weight: 400;
+    line-height: 1.7;
+    color: #e2e8f0;
+    background-color: #0f172a;
+    margin: 0;
+    padding: 0;
+    word-wrap: break-word;
+    font-feature-settings: "kern" 1, "liga" 1, "calt" 1;
+}
+
+.markdown-body {
+    max-width: 100%;
+    margin: 0 auto;
+    padding: 56px 64px;
+}
+
+/* ==================== Typography ==================== */
+
+/* Headings */
+h1, h2, h3, h4, h5, h6 {
+    font-weight: 700;
+    line-height: 1.35;
+    color: #f1f5f9;
+    margin-top: 2em;
+    margin-bottom: 0.8em;
+    letter-spacing: -0.02em;
+    page-break-after: avoid;
+    page-break-inside: avoid;
+}
+
+h1:first-child, h2:first-child, h3:first-child,
+h4:first-child, h5:first-child, h6:first-child {
+    margin-top: 0;
+}
+
+h1 {
+    font-size: 2.4rem;
+    font-weight: 800;
+    letter-spacing: -0.03em;
+    color: #f8fafc;
+    padding-bottom: 0.5em;
+    margin-bottom: 1.2em;
+    border-bottom: 3px solid #334155;
+}
+
+h2 {
+    font-size: 1.8rem;
+    font-weight: 700;
+    color: #f1f5f9;
+    padding-bottom: 0.4em;
+    margin-bottom: 1em;
+    border-bottom: 2px solid #1e293b;
+}
+
+h3 {
+    font-size: 1.4rem;
+    font-weight: 600;
+    color: #e2e8f0;
+}
+
+h4 {
+    font-size: 1.15rem;
+    font-weight: 600;
+    color: #cbd5e1;
+}
+
+h5 {
+    font-size: 1rem;
+    font-weight: 600;
+    color: #94a3b8;
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+}
+
+h6 {
+    font-size: 0.9rem;
+    font-weight: 600;
+    color: #64748b;
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+}
+
+/* Paragraphs */
+p {
+    margin-top: 0;
+    margin-bottom: 1.35em;
+    line-height: 1.75;
+}
+
+/* Links */
+a {
+    color: #60a5fa;
+    text-decoration: none;
+    border-bottom: 1px solid transparent;
+    transition: border-color 0.15s ease;
+}
+
+a:hover {
+    border-bottom-color: #60a5fa;
+}
+
+/* ==================== Code ==================== */
+
+/* Inline code */
+code {
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.88em;
+    font-weight: 500;
+    padding: 0.2em 0.45em;
+    background: linear-gradient(135deg, #1e293b 0%, #1a2332 100%);
+    border-radius: 5px;
+    color: #f472b6;
+    border: 1px solid #334155;
+    white-space: nowrap;
+}
+
+/* Wraps a highlighted block plus its optional language-label header bar so
+   the two never separate across a page break. */
+.code-block {
+    position: relative;
+    margin-bottom: 1.6em;
+    page-break-inside: avoid;
+}
+
+.code-block-label {
+    display: inline-block;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.72rem;
+    font-weight: 600;
+    letter-spacing: 0.04em;
+    text-transform: uppercase;
+    color: #818cf8;
+    background: #1e293b;
+    padding: 0.3em 0.9em;
+    border: 1px solid #334155;
+    border-bottom: none;
+    border-radius: 8px 8px 0 0;
+    margin-bottom: -1px;
+}
+
+.code-block-label + pre {
+    border-top-left-radius: 0;
+}
+
+.code-copy-button {
+    position: absolute;
+    top: 0.6em;
+    right: 0.6em;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.7rem;
+    font-weight: 600;
+    color: #818cf8;
+    background: #1e293b;
+    border: 1px solid #334155;
+    border-radius: 6px;
+    padding: 0.25em 0.7em;
+    cursor: pointer;
+}
+
+.code-copy-button:hover {
+    background: #334155;
+}
+
+/* Code blocks - syntect generates pre with inline styles */
+pre {
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.88rem;
+    line-height: 1.65;
+    padding: 1.3em 1.5em;
+    overflow-x: auto;
+    background: linear-gradient(180deg, #1e293b 0%, #172033 100%) !important;
+    border-radius: 10px;
+    border: 1px solid #334155;
+    margin-top: 0;
+    margin-bottom: 0;
+    box-shadow: 0 4px 16px rgba(0, 0, 0, 0.3);
+}
+
+pre code {
+    font-size: inherit;
+    font-weight: 400;
+    padding: 0;
+    background: transparent !important;
+    border: none;
+    border-radius: 0;
+    color: inherit;
+    white-space: pre;
+}
+
+.code-line {
+    display: block;
+}
+
+/* Opt-in gutter: a CSS counter rather than literal numbers in the markup,
+   so selecting/copying code never picks up the line numbers. */
+pre.with-line-numbers {
+    counter-reset: line;
+}
+
+pre.with-line-numbers .code-line {
+    counter-increment: line;
+    padding-left: 3.5em;
+    position: relative;
+}
+
+pre.with-line-numbers .code-line::before {
+    content: counter(line);
+    position: absolute;
+    left: 0;
+    width: 2.8em;
+    text-align: right;
+    color: #64748b;
+    user-select: none;
+}
+
+.highlighted-line {
+    background: rgba(129, 140, 248, 0.14);
+    margin: 0 -1.5em;
+    padding-left: 1.5em;
+    padding-right: 1.5em;
+}
+
+pre.with-line-numbers .highlighted-line {
+    padding-left: calc(3.5em + 1.5em);
+}
+
+/* ==================== Blockquotes ==================== */
+
+blockquote {
+    margin: 0 0 1.5em 0;
+    padding: 1em 1.5em;
+    color: #94a3b8;
+    border-left: 4px solid #818cf8;
+    background: linear-gradient(135deg, #1e293b 0%, #1a2438 100%);
+    border-radius: 0 8px 8px 0;
+    font-style: italic;
+}
+
+blockquote p {
+    margin-bottom: 0.6em;
+}
+
+blockquote p:last-child {
+    margin-bottom: 0;
+}
+
+blockquote blockquote {
+    margin-top: 0.8em;
+    border-left-color: #6366f1;
+}
+
+blockquote code {
+    font-style: normal;
+}
+
+/* AsciiDoc admonitions (NOTE/TIP/IMPORTANT/WARNING/CAUTION) render as a
+   blockquote with a bold, non-italic label prefix. */
+.admonition-label {
+    font-style: normal;
+    font-weight: 700;
+    text-transform: uppercase;
+    letter-spacing: 0.03em;
+    margin-right: 0.4em;
+}
+
+/* ==================== Lists ==================== */
+
+ul, ol {
+    margin-top: 0;
+    margin-bottom: 1.4em;
+    padding-left: 1.8em;
+}
+
+ul ul, ol ol, ul ol, ol ul {
+    margin-bottom: 0;
+    margin-top: 0.4em;
+}
+
+li {
+    margin-bottom: 0.45em;
+    line-height: 1.7;
+}
+
+li > p {
+    margin-bottom: 0.6em;
+}
+
+li > p:last-child {
+    margin-bottom: 0;
+}
+
+/* Custom bullet styling */
+ul {
+    list-style: none;
+}
+
+ul > li {
+    position: relative;
+    padding-left: 0.2em;
+}
+
+ul > li::before {
+    content: "";
+    position: absolute;
+    left: -1.3em;
+    top: 0.65em;
+    width: 6px;
+    height: 6px;
+    background-color: #818cf8;
+    border-radius: 50%;
+}
+
+ul ul > li::before {
+    background-color: transparent;
+    border: 1.5px solid #818cf8;
+}
+
+ul ul ul > li::before {
+    background-color: #6366f1;
+    border: none;
+    width: 5px;
+    height: 5px;
+}
+
+ol {
+    list-style: none;
+    counter-reset: ol-counter;
+}
+
+ol > li {
+    position: relative;
+    padding-left: 0.3em;
+    counter-increment: ol-counter;
+}
+
+ol > li::before {
+    content: counter(ol-counter) ".";
+    position: absolute;
+    left: -1.8em;
+    top: 0;
+    font-weight: 600;
+    font-size: 0.9em;
+    color: #818cf8;
+    min-width: 1.5em;
+    text-align: right;
+}
+
+/* Task lists */
+li input[type="checkbox"] {
+    margin-right: 0.6em;
+    margin-left: -0.2em;
+    vertical-align: middle;
+    position: relative;
+    top: -1px;
+    width: 16px;
+    height: 16px;
+    accent-color: #818cf8;
+}
+
+/* ==================== Table of Contents ==================== */
+
+nav#TOC {
+    margin: 0 0 2.5em;
+    padding: 1.4em 1.8em;
+    background: #1e293b;
+    border: 1px solid #334155;
+    border-radius: 10px;
+    page-break-inside: avoid;
+}
+
+nav#TOC ul {
+    list-style: none;
+    margin: 0;
+    padding-left: 1.3em;
+}
+
+nav#TOC > ul {
+    padding-left: 0;
+}
+
+nav#TOC li {
+    margin-bottom: 0.4em;
+    line-height: 1.5;
+}
+
+nav#TOC li::before {
+    display: none;
+}
+
+nav#TOC a {
+    color: #e2e8f0;
+    border-bottom: none;
+}
+
+nav#TOC a:hover {
+    color: #60a5fa;
+}
+
+/* ==================== Tables ==================== */
+
+table {
+    border-spacing: 0;
+    border-collapse: separate;
+    border-radius: 10px;
+    margin-top: 0;
+    margin-bottom: 1.8em;
+    width: 100%;
+    overflow: hidden;
+    box-shadow: 0 4px 20px rgba(0, 0, 0, 0.25);
+    page-break-inside: avoid;
+}
+
+thead {
+    display: table-header-group;
+}
+
+tbody {
+    display: table-row-group;
+}
+
+th, td {
+    padding: 0.85em 1.1em;
+    text-align: left;
+    border-bottom: 1px solid #334155;
+}
+
+th {
+    font-weight: 600;
+    font-size: 0.9em;
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+    color: #94a3b8;
+    background: linear-gradient(180deg, #1e293b 0%, #172033 100%);
+    border-bottom: 2px solid #475569;
+}
+
+td {
+    color: #cbd5e1;
+}
+
+tr:last-child td {
+    border-bottom: none;
+}
+
+tbody tr:nth-child(even) {
+    background-color: rgba(30, 41, 59, 0.5);
+}
+
+tbody tr:hover {
+    background-color: rgba(51, 65, 85, 0.4);
+}
+
+/* ==================== Other Elements ==================== */
+
+/* Horizontal rule */
+hr {
+    height: 0;
+    padding: 0;
+    margin: 2.5em 0;
+    border: 0;
+    border-top: 2px solid #334155;
+    background: transparent;
+}
+
+/* Images */
+img {
+    max-width: 100%;
+    height: auto;
+    display: block;
+    margin: 1.5em auto;
+    border-radius: 8px;
+    box-shadow: 0 8px 32px rgba(0, 0, 0, 0.35);
+}
+
+/* Strikethrough */
+del {
+    color: #64748b;
+    text-decoration: line-through;
+    text-decoration-color: #475569;
+}
+
+/* Strong and emphasis */
+strong {
+    font-weight: 650;
+    color: #f1f5f9;
+}
+
+em {
+    font-style: italic;
+    color: #cbd5e1;
+}
+
+/* Definition lists */
+dt {
+    font-weight: 600;
+    margin-top: 1.2em;
+    color: #e2e8f0;
+}
+
+dd {
+    margin-left: 1.8em;
+    margin-bottom: 0.6em;
+    color: #94a3b8;
+}
+
+/* Footnotes */
+.footnote-definition {
+    font-size: 0.88rem;
+    margin-top: 2.5em;
+    padding-top: 1.2em;
+    border-top: 2px solid #334155;
+    color: #94a3b8;
+}
+
+/* Keyboard shortcut styling */
+kbd {
+    font-family: inherit;
+    font-size: 0.85em;
+    padding: 0.15em 0.4em;
+    background: linear-gradient(180deg, #334155 0%, #1e293b 100%);
+    border: 1px solid #475569;
+    border-radius: 4px;
+    color: #e2e8f0;
+    box-shadow: 0 1px 2px rgba(0,0,0,0.3), inset 0 -1px 0 rgba(0,0,0,0.2);
+}
+
+/* ==================== Print Optimizations ==================== */
+
+@media print {
+    html {
+        font-size: 14px;
+    }
+
+    body {
+        background: #0f172a;
+    }
+
+    .markdown-body {
+        padding: 40px 48px;
+    }
+
+    .code-block, blockquote, table, img, h1, h2, h3, h4, h5, h6 {
+        page-break-inside: avoid;
+    }
+
+    h1, h2, h3, h4, h5, h6 {
+        page-break-after: avoid;
+    }
+
+    p, li {
+        orphans: 3;
+        widows: 3;
+    }
+
+    a {
+        color: #60a5fa;
+    }
+
+    a[href^="http"]::after {
+        content: " (" attr(href) ")";
+        font-size: 0.8em;
+        color: #64748b;
+        word-break: break-all;
+    }
+
+    table {
+        box-shadow: none;
+        border: 1px solid #475569;
+    }
+
+    img {
+        box-shadow: none;
+        border: 1px solid #334155;
+    }
+
+    pre {
+        box-shadow: none;
+        border: 1px solid #475569;
+    }
+}
+"#;
+// Adaptive "auto" theme CSS - driven by CSS custom properties, with a
+// dark palette in :root overridden by a light one under
+// prefers-color-scheme, so exported HTML follows the viewer's OS setting.
+const ADAPTIVE_THEME_CSS: &str = r#":root {
+    --bg: #0f172a;
+    --fg: #e2e8f0;
+    --heading: #f1f5f9;
+    --text-soft: #cbd5e1;
+    --text-mute: #94a3b8;
+    --text-faint: #64748b;
+    --surface: #1e293b;
+    --surface-end: #172033;
+    --surface-alt-end: #1a2332;
+    --surface-alt-end-2: #1a2438;
+    --border: #334155;
+    --border-soft: #475569;
+    --accent: #818cf8;
+    --link: #60a5fa;
+    --accent-strong: #6366f1;
+    --code-accent: #f472b6;
+}
+
+@media (prefers-color-scheme: light) {
+    :root {
+        --bg: #ffffff;
+        --fg: #1a1a2e;
+        --heading: #0f0f23;
+        --text-soft: #2d3748;
+        --text-mute: #4a5568;
+        --text-faint: #718096;
+        --surface: #fafbfd;
+        --surface-end: #f5f7fa;
+        --surface-alt-end: #f1f3f8;
+        --surface-alt-end-2: #f1f3f8;
+        --border: #e2e6ee;
+        --border-soft: #d1d5db;
+        --accent: #6366f1;
+        --link: #2563eb;
+        --accent-strong: #6366f1;
+        --code-accent: #c41d7f;
+    }
+}
+
+@page {
+    margin: 0;
+    size: auto;
+}
+
+*, *::before, *::after {
+    box-sizing: border-box;
+}
+
+html {
+    font-size: 15px;
+    -webkit-print-color-adjust: exact;
+    print-color-adjust: exact;
+    text-rendering: optimizeLegibility;
+    -webkit-font-smoothing: antialiased;
+    -moz-osx-font-smoothing: grayscale;
+}
+
+body {
+    font-family: "Inter", -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial, sans-serif,
+        "PingFang SC", "Hiragino Sans GB", "Microsoft YaHei",
+        "Hiragino Kaku Gothic Pro", "Yu Gothic",
+        "Apple SD Gothic Neo", "Malgun Gothic",
+        "Apple Color Emoji", "Segoe UI Emoji";
+    font-size: 1rem;
+    font-weight: 400;
+    line-height: 1.7;
+    color: var(--fg);
+    background-color: var(--bg);
+    margin: 0;
+    padding: 0;
+    word-wrap: break-word;
+    font-feature-settings: "kern" 1, "liga" 1, "calt" 1;
+}
+
+.markdown-body {
+    max-width: 100%;
+    margin: 0 auto;
+    padding: 56px 64px;
+}
+
+/* ==================== Typography ==================== */
+
+/* Headings */
+h1, h2, h3, h4, h5, h6 {
+    font-weight: 700;
+    line-height: 1.35;
+    color: var(--heading);
+    margin-top: 2em;
+    margin-bottom: 0.8em;
+    letter-spacing: -0.02em;
+    page-break-after: avoid;
+    page-break-inside: avoid;
+}
+
+h1:first-child, h2:first-child, h3:first-child,
+h4:first-child, h5:first-child, h6:first-child {
+    margin-top: 0;
+}
+
+h1 {
+    font-size: 2.4rem;
+    font-weight: 800;
+    letter-spacing: -0.03em;
+    color: var(--heading);
+    padding-bottom: 0.5em;
+    margin-bottom: 1.2em;
+    border-bottom: 3px solid var(--border);
+}
+
+h2 {
+    font-size: 1.8rem;
+    font-weight: 700;
+    color: var(--heading);
+    padding-bottom: 0.4em;
+    margin-bottom: 1em;
+    border-bottom: 2px solid var(--surface);
+}
+
+h3 {
+    font-size: 1.4rem;
+    font-weight: 600;
+    color: var(--fg);
+}
+
+h4 {
+    font-size: 1.15rem;
+    font-weight: 600;
+    color: var(--text-soft);
+}
+
+h5 {
+    font-size: 1rem;
+    font-weight: 600;
+    color: var(--text-mute);
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+}
+
+h6 {
+    font-size: 0.9rem;
+    font-weight: 600;
+    color: var(--text-faint);
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+}
+
+/* Paragraphs */
+p {
+    margin-top: 0;
+    margin-bottom: 1.35em;
+    line-height: 1.75;
+}
+
+/* Links */
+a {
+    color: var(--link);
+    text-decoration: none;
+    border-bottom: 1px solid transparent;
+    transition: border-color 0.15s ease;
+}
+
+a:hover {
+    border-bottom-color: var(--link);
+}
+
+/* ==================== Code ==================== */
+
+/* Inline code */
+code {
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.88em;
+    font-weight: 500;
+    padding: 0.2em 0.45em;
+    background: linear-gradient(135deg, var(--surface) 0%, var(--surface-alt-end) 100%);
+    border-radius: 5px;
+    color: var(--code-accent);
+    border: 1px solid var(--border);
+    white-space: nowrap;
+}
+
+/* Wraps a highlighted block plus its optional language-label header bar so
+   the two never separate across a page break. */
+.code-block {
+    position: relative;
+    margin-bottom: 1.6em;
+    page-break-inside: avoid;
+}
+
+.code-block-label {
+    display: inline-block;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.72rem;
+    font-weight: 600;
+    letter-spacing: 0.04em;
+    text-transform: uppercase;
+    color: var(--accent);
+    background: var(--surface);
+    padding: 0.3em 0.9em;
+    border: 1px solid var(--border);
+    border-bottom: none;
+    border-radius: 8px 8px 0 0;
+    margin-bottom: -1px;
+}
+
+.code-block-label + pre {
+    border-top-left-radius: 0;
+}
+
+.code-copy-button {
+    position: absolute;
+    top: 0.6em;
+    right: 0.6em;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.7rem;
+    font-weight: 600;
+    color: var(--accent);
+    background: var(--surface);
+    border: 1px solid var(--border);
+    border-radius: 6px;
+    padding: 0.25em 0.7em;
+    cursor: pointer;
+}
+
+.code-copy-button:hover {
+    background: var(--border);
+}
+
+/* Code blocks - syntect generates pre with inline styles */
+pre {
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.88rem;
+    line-height: 1.65;
+    padding: 1.3em 1.5em;
+    overflow-x: auto;
+    background: linear-gradient(180deg, var(--surface) 0%, var(--surface-end) 100%) !important;
+    border-radius: 10px;
+    border: 1px solid var(--border);
+    margin-top: 0;
+    margin-bottom: 0;
+    box-shadow: 0 4px 16px rgba(0, 0, 0, 0.3);
+}
+
+pre code {
+    font-size: inherit;
+    font-weight: 400;
+    padding: 0;
+    background: transparent !important;
+    border: none;
+    border-radius: 0;
+    color: inherit;
+    white-space: pre;
+}
+
+.code-line {
+    display: block;
+}
+
+/* Opt-in gutter: a CSS counter rather than literal numbers in the markup,
+   so selecting/copying code never picks up the line numbers. */
+pre.with-line-numbers {
+    counter-reset: line;
+}
+
+pre.with-line-numbers .code-line {
+    counter-increment: line;
+    padding-left: 3.5em;
+    position: relative;
+}
+
+pre.with-line-numbers .code-line::before {
+    content: counter(line);
+    position: absolute;
+    left: 0;
+    width: 2.8em;
+    text-align: right;
+    color: var(--text-faint);
+    user-select: none;
+}
+
+.highlighted-line {
+    background: rgba(129, 140, 248, 0.14);
+    margin: 0 -1.5em;
+    padding-left: 1.5em;
+    padding-right: 1.5em;
+}
+
+pre.with-line-numbers .highlighted-line {
+    padding-left: calc(3.5em + 1.5em);
+}
+
+/* ==================== Blockquotes ==================== */
+
+blockquote {
+    margin: 0 0 1.5em 0;
+    padding: 1em 1.5em;
+    color: var(--text-mute);
+    border-left: 4px solid var(--accent);
+    background: linear-gradient(135deg, var(--surface) 0%, var(--surface-alt-end-2) 100%);
+    border-radius: 0 8px 8px 0;
+    font-style: italic;
+}
+
+blockquote p {
+    margin-bottom: 0.6em;
+}
+
+blockquote p:last-child {
+    margin-bottom: 0;
+}
+
+blockquote blockquote {
+    margin-top: 0.8em;
+    border-left-color: var(--accent-strong);
+}
+
+blockquote code {
+    font-style: normal;
+}
+
+/* AsciiDoc admonitions (NOTE/TIP/IMPORTANT/WARNING/CAUTION) render as a
+   blockquote with a bold, non-italic label prefix. */
+.admonition-label {
+    font-style: normal;
+    font-weight: 700;
+    text-transform: uppercase;
+    letter-spacing: 0.03em;
+    margin-right: 0.4em;
+}
+
+/* ==================== Lists ==================== */
+
+ul, ol {
+    margin-top: 0;
+    margin-bottom: 1.4em;
+    padding-left: 1.8em;
+}
+
+ul ul, ol ol, ul ol, ol ul {
+    margin-bottom: 0;
+    margin-top: 0.4em;
+}
+
+li {
+    margin-bottom: 0.45em;
+    line-height: 1.7;
+}
+
+li > p {
+    margin-bottom: 0.6em;
+}
+
+li > p:last-child {
+    margin-bottom: 0;
+}
+
+/* Custom bullet styling */
+ul {
+    list-style: none;
+}
+
+ul > li {
+    position: relative;
+    padding-left: 0.2em;
+}
+
+ul > li::before {
+    content: "";
+    position: absolute;
+    left: -1.3em;
+    top: 0.65em;
+    width: 6px;
+    height: 6px;
+    background-color: var(--accent);
+    border-radius: 50%;
+}
+
+ul ul > li::before {
+    background-color: transparent;
+    border: 1.5px solid var(--accent);
+}
+
+ul ul ul > li::before {
+    background-color: var(--accent-strong);
+    border: none;
+    width: 5px;
+    height: 5px;
+}
+
+ol {
+    list-style: none;
+    counter-reset: ol-counter;
+}
+
+ol > li {
+    position: relative;
+    padding-left: 0.3em;
+    counter-increment: ol-counter;
+}
+
+ol > li::before {
+    content: counter(ol-counter) ".";
+    position: absolute;
+    left: -1.8em;
+    top: 0;
+    font-weight: 600;
+    font-size: 0.9em;
+    color: var(--accent);
+    min-width: 1.5em;
+    text-align: right;
+}
+
+/* Task lists */
+li input[type="checkbox"] {
+    margin-right: 0.6em;
+    margin-left: -0.2em;
+    vertical-align: middle;
+    position: relative;
+    top: -1px;
+    width: 16px;
+    height: 16px;
+    accent-color: var(--accent);
+}
+
+/* ==================== Table of Contents ==================== */
+
+nav#TOC {
+    margin: 0 0 2.5em;
+    padding: 1.4em 1.8em;
+    background: var(--surface);
+    border: 1px solid var(--border);
+    border-radius: 10px;
+    page-break-inside: avoid;
+}
+
+nav#TOC ul {
+    list-style: none;
+    margin: 0;
+    padding-left: 1.3em;
+}
+
+nav#TOC > ul {
+    padding-left: 0;
+}
+
+nav#TOC li {
+    margin-bottom: 0.4em;
+    line-height: 1.5;
+}
+
+nav#TOC li::before {
+    display: none;
+}
+
+nav#TOC a {
+    color: var(--fg);
+    border-bottom: none;
+}
+
+nav#TOC a:hover {
+    color: var(--link);
+}
+
+/* ==================== Tables ==================== */
+
+table {
+    border-spacing: 0;
+    border-collapse: separate;
+    border-radius: 10px;
+    margin-top: 0;
+    margin-bottom: 1.8em;
+    width: 100%;
+    overflow: hidden;
+    box-shadow: 0 4px 20px rgba(0, 0, 0, 0.25);
+    page-break-inside: avoid;
+}
+
+thead {
+    display: table-header-group;
+}
+
+tbody {
+    display: table-row-group;
+}
+
+th, td {
+    padding: 0.85em 1.1em;
+    text-align: left;
+    border-bottom: 1px solid var(--border);
+}
+
+th {
+    font-weight: 600;
+    font-size: 0.9em;
+    text-transform: uppercase;
+    letter-spacing: 0.04em;
+    color: var(--text-mute);
+    background: linear-gradient(180deg, var(--surface) 0%, var(--surface-end) 100%);
+    border-bottom: 2px solid var(--border-soft);
+}
+
+td {
+    color: var(--text-soft);
+}
+
+tr:last-child td {
+    border-bottom: none;
+}
+
+tbody tr:nth-child(even) {
+    background-color: rgba(30, 41, 59, 0.5);
+}
+
+tbody tr:hover {
+    background-color: rgba(51, 65, 85, 0.4);
+}
+
+/* ==================== Other Elements ==================== */
+
+/* Horizontal rule */
+hr {
+    height: 0;
+    padding: 0;
+    margin: 2.5em 0;
+    border: 0;
+    border-top: 2px solid var(--border);
+    background: transparent;
+}
+
+/* Images */
+img {
+    max-width: 100%;
+    height: auto;
+    display: block;
+    margin: 1.5em auto;
+    border-radius: 8px;
+    box-shadow: 0 8px 32px rgba(0, 0, 0, 0.35);
+}
+
+/* Strikethrough */
+del {
+    color: var(--text-faint);
+    text-decoration: line-through;
+    text-decoration-color: var(--border-soft);
+}
+
+/* Strong and emphasis */
+strong {
+    font-weight: 650;
+    color: var(--heading);
+}
+
+em {
+    font-style: italic;
+    color: var(--text-soft);
+}
+
+/* Definition lists */
+dt {
+    font-weight: 600;
+    margin-top: 1.2em;
+    color: var(--fg);
+}
+
+dd {
+    margin-left: 1.8em;
+    margin-bottom: 0.6em;
+    color: var(--text-mute);
+}
+
+/* Footnotes */
+.footnote-definition {
+    font-size: 0.88rem;
+    margin-top: 2.5em;
+    padding-top: 1.2em;
+    border-top: 2px solid var(--border);
+    color: var(--text-mute);
+}
+
+/* Keyboard shortcut styling */
+kbd {
+    font-family: inherit;
+    font-size: 0.85em;
+    padding: 0.15em 0.4em;
+    background: linear-gradient(180deg, var(--border) 0%, var(--surface) 100%);
+    border: 1px solid var(--border-soft);
+    border-radius: 4px;
+    color: var(--fg);
+    box-shadow: 0 1px 2px rgba(0,0,0,0.3), inset 0 -1px 0 rgba(0,0,0,0.2);
+}
+
+/* ==================== Print Optimizations ==================== */
+
+@media print {
+    html {
+        font-size: 14px;
+    }
+
+    body {
+        background: var(--bg);
+    }
+
+    .markdown-body {
+        padding: 40px 48px;
+    }
+
+    .code-block, blockquote, table, img, h1, h2, h3, h4, h5, h6 {
+        page-break-inside: avoid;
+    }
+
+    h1, h2, h3, h4, h5, h6 {
+        page-break-after: avoid;
+    }
+
+    p, li {
+        orphans: 3;
+        widows: 3;
+    }
+
+    a {
+        color: var(--link);
+    }
+
+    a[href^="http"]::after {
+        content: " (" attr(href) ")";
+        font-size: 0.8em;
+        color: var(--text-faint);
+        word-break: break-all;
+    }
+
+    table {
+        box-shadow: none;
+        border: 1px solid var(--border-soft);
+    }
+
+    img {
+        box-shadow: none;
+        border: 1px solid var(--border);
+    }
+
+    pre {
+        box-shadow: none;
+        border: 1px solid var(--border-soft);
+    }
+}
+"#;
+
+
+const AZURE_THEME_CSS: &str = r#"
 @page {
     margin: 0;
     size: auto;
@@ -467,8 +3215,8 @@ body {
     font-size: 1rem;
     font-weight: 400;
     line-height: 1.7;
-    color: #1a1a2e;
-    background-color: #ffffff;
+    color: #e2e8f0;
+    background-color: #0f172a;
     margin: 0;
     padding: 0;
     word-wrap: break-word;
@@ -487,7 +3235,7 @@ body {
 h1, h2, h3, h4, h5, h6 {
     font-weight: 700;
     line-height: 1.35;
-    color: #0f0f23;
+    color: #f1f5f9;
     margin-top: 2em;
     margin-bottom: 0.8em;
     letter-spacing: -0.02em;
@@ -504,37 +3252,37 @@ h1 {
     font-size: 2.4rem;
     font-weight: 800;
     letter-spacing: -0.03em;
-    color: #0a0a1a;
+    color: #f8fafc;
     padding-bottom: 0.5em;
     margin-bottom: 1.2em;
-    border-bottom: 3px solid #e8e8f0;
+    border-bottom: 3px solid #334155;
 }
 
 h2 {
     font-size: 1.8rem;
     font-weight: 700;
-    color: #16163a;
+    color: #f1f5f9;
     padding-bottom: 0.4em;
     margin-bottom: 1em;
-    border-bottom: 2px solid #ececf4;
+    border-bottom: 2px solid #1e293b;
 }
 
 h3 {
     font-size: 1.4rem;
     font-weight: 600;
-    color: #1f1f4a;
+    color: #e2e8f0;
 }
 
 h4 {
     font-size: 1.15rem;
     font-weight: 600;
-    color: #2a2a5a;
+    color: #cbd5e1;
 }
 
 h5 {
     font-size: 1rem;
     font-weight: 600;
-    color: #3a3a6a;
+    color: #94a3b8;
     text-transform: uppercase;
     letter-spacing: 0.04em;
 }
@@ -542,7 +3290,7 @@ h5 {
 h6 {
     font-size: 0.9rem;
     font-weight: 600;
-    color: #5a5a8a;
+    color: #64748b;
     text-transform: uppercase;
     letter-spacing: 0.04em;
 }
@@ -556,14 +3304,14 @@ p {
 
 /* Links */
 a {
-    color: #2563eb;
+    color: #0ea5e9;
     text-decoration: none;
     border-bottom: 1px solid transparent;
     transition: border-color 0.15s ease;
 }
 
 a:hover {
-    border-bottom-color: #2563eb;
+    border-bottom-color: #0ea5e9;
 }
 
 /* ==================== Code ==================== */
@@ -574,13 +3322,60 @@ code {
     font-size: 0.88em;
     font-weight: 500;
     padding: 0.2em 0.45em;
-    background: linear-gradient(135deg, #f8f9fc 0%, #f1f3f8 100%);
+    background: linear-gradient(135deg, #1e293b 0%, #1a2332 100%);
     border-radius: 5px;
-    color: #c41d7f;
-    border: 1px solid #e4e7ee;
+    color: #7dd3fc;
+    border: 1px solid #334155;
     white-space: nowrap;
 }
 
+/* Wraps a highlighted block plus its optional language-label header bar so
+   the two never separate across a page break. */
+.code-block {
+    position: relative;
+    margin-bottom: 1.6em;
+    page-break-inside: avoid;
+}
+
+.code-block-label {
+    display: inline-block;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.72rem;
+    font-weight: 600;
+    letter-spacing: 0.04em;
+    text-transform: uppercase;
+    color: #38bdf8;
+    background: #1e293b;
+    padding: 0.3em 0.9em;
+    border: 1px solid #334155;
+    border-bottom: none;
+    border-radius: 8px 8px 0 0;
+    margin-bottom: -1px;
+}
+
+.code-block-label + pre {
+    border-top-left-radius: 0;
+}
+
+.code-copy-button {
+    position: absolute;
+    top: 0.6em;
+    right: 0.6em;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.7rem;
+    font-weight: 600;
+    color: #38bdf8;
+    background: #1e293b;
+    border: 1px solid #334155;
+    border-radius: 6px;
+    padding: 0.25em 0.7em;
+    cursor: pointer;
+}
+
+.code-copy-button:hover {
+    background: #334155;
+}
+
 /* Code blocks - syntect generates pre with inline styles */
 pre {
     font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
@@ -588,13 +3383,12 @@ pre {
     line-height: 1.65;
     padding: 1.3em 1.5em;
     overflow-x: auto;
-    background: linear-gradient(180deg, #fafbfd 0%, #f5f7fa 100%) !important;
+    background: linear-gradient(180deg, #1e293b 0%, #172033 100%) !important;
     border-radius: 10px;
-    border: 1px solid #e2e6ee;
+    border: 1px solid #334155;
     margin-top: 0;
-    margin-bottom: 1.6em;
-    page-break-inside: avoid;
-    box-shadow: 0 2px 8px rgba(0, 0, 0, 0.04);
+    margin-bottom: 0;
+    box-shadow: 0 4px 16px rgba(0, 0, 0, 0.3);
 }
 
 pre code {
@@ -608,14 +3402,51 @@ pre code {
     white-space: pre;
 }
 
+.code-line {
+    display: block;
+}
+
+/* Opt-in gutter: a CSS counter rather than literal numbers in the markup,
+   so selecting/copying code never picks up the line numbers. */
+pre.with-line-numbers {
+    counter-reset: line;
+}
+
+pre.with-line-numbers .code-line {
+    counter-increment: line;
+    padding-left: 3.5em;
+    position: relative;
+}
+
+pre.with-line-numbers .code-line::before {
+    content: counter(line);
+    position: absolute;
+    left: 0;
+    width: 2.8em;
+    text-align: right;
+    color: #64748b;
+    user-select: none;
+}
+
+.highlighted-line {
+    background: rgba(129, 140, 248, 0.14);
+    margin: 0 -1.5em;
+    padding-left: 1.5em;
+    padding-right: 1.5em;
+}
+
+pre.with-line-numbers .highlighted-line {
+    padding-left: calc(3.5em + 1.5em);
+}
+
 /* ==================== Blockquotes ==================== */
 
 blockquote {
     margin: 0 0 1.5em 0;
     padding: 1em 1.5em;
-    color: #4a5568;
-    border-left: 4px solid #6366f1;
-    background: linear-gradient(135deg, #f8f9ff 0%, #f3f4fc 100%);
+    color: #94a3b8;
+    border-left: 4px solid #38bdf8;
+    background: linear-gradient(135deg, #1e293b 0%, #1a2438 100%);
     border-radius: 0 8px 8px 0;
     font-style: italic;
 }
@@ -630,13 +3461,23 @@ blockquote p:last-child {
 
 blockquote blockquote {
     margin-top: 0.8em;
-    border-left-color: #a5b4fc;
+    border-left-color: #0284c7;
 }
 
 blockquote code {
     font-style: normal;
 }
 
+/* AsciiDoc admonitions (NOTE/TIP/IMPORTANT/WARNING/CAUTION) render as a
+   blockquote with a bold, non-italic label prefix. */
+.admonition-label {
+    font-style: normal;
+    font-weight: 700;
+    text-transform: uppercase;
+    letter-spacing: 0.03em;
+    margin-right: 0.4em;
+}
+
 /* ==================== Lists ==================== */
 
 ul, ol {
@@ -680,17 +3521,17 @@ ul > li::before {
     top: 0.65em;
     width: 6px;
     height: 6px;
-    background-color: #6366f1;
+    background-color: #38bdf8;
     border-radius: 50%;
 }
 
 ul ul > li::before {
     background-color: transparent;
-    border: 1.5px solid #6366f1;
+    border: 1.5px solid #38bdf8;
 }
 
 ul ul ul > li::before {
-    background-color: #a5b4fc;
+    background-color: #0284c7;
     border: none;
     width: 5px;
     height: 5px;
@@ -714,7 +3555,7 @@ ol > li::before {
     top: 0;
     font-weight: 600;
     font-size: 0.9em;
-    color: #6366f1;
+    color: #38bdf8;
     min-width: 1.5em;
     text-align: right;
 }
@@ -728,7 +3569,46 @@ li input[type="checkbox"] {
     top: -1px;
     width: 16px;
     height: 16px;
-    accent-color: #6366f1;
+    accent-color: #38bdf8;
+}
+
+/* ==================== Table of Contents ==================== */
+
+nav#TOC {
+    margin: 0 0 2.5em;
+    padding: 1.4em 1.8em;
+    background: #1e293b;
+    border: 1px solid #334155;
+    border-radius: 10px;
+    page-break-inside: avoid;
+}
+
+nav#TOC ul {
+    list-style: none;
+    margin: 0;
+    padding-left: 1.3em;
+}
+
+nav#TOC > ul {
+    padding-left: 0;
+}
+
+nav#TOC li {
+    margin-bottom: 0.4em;
+    line-height: 1.5;
+}
+
+nav#TOC li::before {
+    display: none;
+}
+
+nav#TOC a {
+    color: #e2e8f0;
+    border-bottom: none;
+}
+
+nav#TOC a:hover {
+    color: #0ea5e9;
 }
 
 /* ==================== Tables ==================== */
@@ -741,7 +3621,7 @@ table {
     margin-bottom: 1.8em;
     width: 100%;
     overflow: hidden;
-    box-shadow: 0 2px 12px rgba(0, 0, 0, 0.06);
+    box-shadow: 0 4px 20px rgba(0, 0, 0, 0.25);
     page-break-inside: avoid;
 }
 
@@ -756,7 +3636,7 @@ tbody {
 th, td {
     padding: 0.85em 1.1em;
     text-align: left;
-    border-bottom: 1px solid #e8ebf0;
+    border-bottom: 1px solid #334155;
 }
 
 th {
@@ -764,13 +3644,13 @@ th {
     font-size: 0.9em;
     text-transform: uppercase;
     letter-spacing: 0.04em;
-    color: #4a5080;
-    background: linear-gradient(180deg, #f8f9fc 0%, #f1f3f8 100%);
-    border-bottom: 2px solid #dde0e8;
+    color: #94a3b8;
+    background: linear-gradient(180deg, #1e293b 0%, #172033 100%);
+    border-bottom: 2px solid #475569;
 }
 
 td {
-    color: #2d3748;
+    color: #cbd5e1;
 }
 
 tr:last-child td {
@@ -778,11 +3658,11 @@ tr:last-child td {
 }
 
 tbody tr:nth-child(even) {
-    background-color: #fafbfd;
+    background-color: rgba(30, 41, 59, 0.5);
 }
 
 tbody tr:hover {
-    background-color: #f5f6fa;
+    background-color: rgba(51, 65, 85, 0.4);
 }
 
 /* ==================== Other Elements ==================== */
@@ -793,7 +3673,7 @@ hr {
     padding: 0;
     margin: 2.5em 0;
     border: 0;
-    border-top: 2px solid #e8ebf0;
+    border-top: 2px solid #334155;
     background: transparent;
 }
 
@@ -804,38 +3684,38 @@ img {
     display: block;
     margin: 1.5em auto;
     border-radius: 8px;
-    box-shadow: 0 4px 16px rgba(0, 0, 0, 0.08);
+    box-shadow: 0 8px 32px rgba(0, 0, 0, 0.35);
 }
 
 /* Strikethrough */
 del {
-    color: #718096;
+    color: #64748b;
     text-decoration: line-through;
-    text-decoration-color: #cbd5e0;
+    text-decoration-color: #475569;
 }
 
 /* Strong and emphasis */
 strong {
     font-weight: 650;
-    color: #0f0f23;
+    color: #f1f5f9;
 }
 
 em {
     font-style: italic;
-    color: #2d3748;
+    color: #cbd5e1;
 }
 
 /* Definition lists */
 dt {
     font-weight: 600;
     margin-top: 1.2em;
-    color: #1a1a2e;
+    color: #e2e8f0;
 }
 
 dd {
     margin-left: 1.8em;
     margin-bottom: 0.6em;
-    color: #4a5568;
+    color: #94a3b8;
 }
 
 /* Footnotes */
@@ -843,8 +3723,8 @@ dd {
     font-size: 0.88rem;
     margin-top: 2.5em;
     padding-top: 1.2em;
-    border-top: 2px solid #e8ebf0;
-    color: #4a5568;
+    border-top: 2px solid #334155;
+    color: #94a3b8;
 }
 
 /* Keyboard shortcut styling */
@@ -852,10 +3732,11 @@ kbd {
     font-family: inherit;
     font-size: 0.85em;
     padding: 0.15em 0.4em;
-    background: linear-gradient(180deg, #fff 0%, #f5f5f5 100%);
-    border: 1px solid #d1d5db;
+    background: linear-gradient(180deg, #334155 0%, #1e293b 100%);
+    border: 1px solid #475569;
     border-radius: 4px;
-    box-shadow: 0 1px 2px rgba(0,0,0,0.08), inset 0 -1px 0 rgba(0,0,0,0.1);
+    color: #e2e8f0;
+    box-shadow: 0 1px 2px rgba(0,0,0,0.3), inset 0 -1px 0 rgba(0,0,0,0.2);
 }
 
 /* ==================== Print Optimizations ==================== */
@@ -866,14 +3747,14 @@ kbd {
     }
 
     body {
-        background: white;
+        background: #0f172a;
     }
 
     .markdown-body {
         padding: 40px 48px;
     }
 
-    pre, blockquote, table, img, h1, h2, h3, h4, h5, h6 {
+    .code-block, blockquote, table, img, h1, h2, h3, h4, h5, h6 {
         page-break-inside: avoid;
     }
 
@@ -887,35 +3768,34 @@ kbd {
     }
 
     a {
-        color: #2563eb;
+        color: #0ea5e9;
     }
 
     a[href^="http"]::after {
         content: " (" attr(href) ")";
         font-size: 0.8em;
-        color: #718096;
+        color: #64748b;
         word-break: break-all;
     }
 
     table {
         box-shadow: none;
-        border: 1px solid #d1d5db;
+        border: 1px solid #475569;
     }
 
     img {
         box-shadow: none;
-        border: 1px solid #e8ebf0;
+        border: 1px solid #334155;
     }
 
     pre {
         box-shadow: none;
-        border: 1px solid #d1d5db;
+        border: 1px solid #475569;
     }
 }
 "#;
 
-// Dark theme CSS - Professional dark document styling
-const DARK_THEME_CSS: &str = r#"
+const TEAL_THEME_CSS: &str = r#"
 @page {
     margin: 0;
     size: auto;
@@ -1032,14 +3912,14 @@ p {
 
 /* Links */
 a {
-    color: #60a5fa;
+    color: #14b8a6;
     text-decoration: none;
     border-bottom: 1px solid transparent;
     transition: border-color 0.15s ease;
 }
 
 a:hover {
-    border-bottom-color: #60a5fa;
+    border-bottom-color: #14b8a6;
 }
 
 /* ==================== Code ==================== */
@@ -1052,11 +3932,58 @@ code {
     padding: 0.2em 0.45em;
     background: linear-gradient(135deg, #1e293b 0%, #1a2332 100%);
     border-radius: 5px;
-    color: #f472b6;
+    color: #5eead4;
     border: 1px solid #334155;
     white-space: nowrap;
 }
 
+/* Wraps a highlighted block plus its optional language-label header bar so
+   the two never separate across a page break. */
+.code-block {
+    position: relative;
+    margin-bottom: 1.6em;
+    page-break-inside: avoid;
+}
+
+.code-block-label {
+    display: inline-block;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.72rem;
+    font-weight: 600;
+    letter-spacing: 0.04em;
+    text-transform: uppercase;
+    color: #2dd4bf;
+    background: #1e293b;
+    padding: 0.3em 0.9em;
+    border: 1px solid #334155;
+    border-bottom: none;
+    border-radius: 8px 8px 0 0;
+    margin-bottom: -1px;
+}
+
+.code-block-label + pre {
+    border-top-left-radius: 0;
+}
+
+.code-copy-button {
+    position: absolute;
+    top: 0.6em;
+    right: 0.6em;
+    font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
+    font-size: 0.7rem;
+    font-weight: 600;
+    color: #2dd4bf;
+    background: #1e293b;
+    border: 1px solid #334155;
+    border-radius: 6px;
+    padding: 0.25em 0.7em;
+    cursor: pointer;
+}
+
+.code-copy-button:hover {
+    background: #334155;
+}
+
 /* Code blocks - syntect generates pre with inline styles */
 pre {
     font-family: "JetBrains Mono", "Fira Code", ui-monospace, SFMono-Regular, "SF Mono", Menlo, Monaco, Consolas, monospace;
@@ -1068,8 +3995,7 @@ pre {
     border-radius: 10px;
     border: 1px solid #334155;
     margin-top: 0;
-    margin-bottom: 1.6em;
-    page-break-inside: avoid;
+    margin-bottom: 0;
     box-shadow: 0 4px 16px rgba(0, 0, 0, 0.3);
 }
 
@@ -1084,13 +4010,50 @@ pre code {
     white-space: pre;
 }
 
+.code-line {
+    display: block;
+}
+
+/* Opt-in gutter: a CSS counter rather than literal numbers in the markup,
+   so selecting/copying code never picks up the line numbers. */
+pre.with-line-numbers {
+    counter-reset: line;
+}
+
+pre.with-line-numbers .code-line {
+    counter-increment: line;
+    padding-left: 3.5em;
+    position: relative;
+}
+
+pre.with-line-numbers .code-line::before {
+    content: counter(line);
+    position: absolute;
+    left: 0;
+    width: 2.8em;
+    text-align: right;
+    color: #64748b;
+    user-select: none;
+}
+
+.highlighted-line {
+    background: rgba(129, 140, 248, 0.14);
+    margin: 0 -1.5em;
+    padding-left: 1.5em;
+    padding-right: 1.5em;
+}
+
+pre.with-line-numbers .highlighted-line {
+    padding-left: calc(3.5em + 1.5em);
+}
+
 /* ==================== Blockquotes ==================== */
 
 blockquote {
     margin: 0 0 1.5em 0;
     padding: 1em 1.5em;
     color: #94a3b8;
-    border-left: 4px solid #818cf8;
+    border-left: 4px solid #2dd4bf;
     background: linear-gradient(135deg, #1e293b 0%, #1a2438 100%);
     border-radius: 0 8px 8px 0;
     font-style: italic;
@@ -1106,13 +4069,23 @@ blockquote p:last-child {
 
 blockquote blockquote {
     margin-top: 0.8em;
-    border-left-color: #6366f1;
+    border-left-color: #0d9488;
 }
 
 blockquote code {
     font-style: normal;
 }
 
+/* AsciiDoc admonitions (NOTE/TIP/IMPORTANT/WARNING/CAUTION) render as a
+   blockquote with a bold, non-italic label prefix. */
+.admonition-label {
+    font-style: normal;
+    font-weight: 700;
+    text-transform: uppercase;
+    letter-spacing: 0.03em;
+    margin-right: 0.4em;
+}
+
 /* ==================== Lists ==================== */
 
 ul, ol {
@@ -1156,17 +4129,17 @@ ul > li::before {
     top: 0.65em;
     width: 6px;
     height: 6px;
-    background-color: #818cf8;
+    background-color: #2dd4bf;
     border-radius: 50%;
 }
 
 ul ul > li::before {
     background-color: transparent;
-    border: 1.5px solid #818cf8;
+    border: 1.5px solid #2dd4bf;
 }
 
 ul ul ul > li::before {
-    background-color: #6366f1;
+    background-color: #0d9488;
     border: none;
     width: 5px;
     height: 5px;
@@ -1190,7 +4163,7 @@ ol > li::before {
     top: 0;
     font-weight: 600;
     font-size: 0.9em;
-    color: #818cf8;
+    color: #2dd4bf;
     min-width: 1.5em;
     text-align: right;
 }
@@ -1204,7 +4177,46 @@ li input[type="checkbox"] {
     top: -1px;
     width: 16px;
     height: 16px;
-    accent-color: #818cf8;
+    accent-color: #2dd4bf;
+}
+
+/* ==================== Table of Contents ==================== */
+
+nav#TOC {
+    margin: 0 0 2.5em;
+    padding: 1.4em 1.8em;
+    background: #1e293b;
+    border: 1px solid #334155;
+    border-radius: 10px;
+    page-break-inside: avoid;
+}
+
+nav#TOC ul {
+    list-style: none;
+    margin: 0;
+    padding-left: 1.3em;
+}
+
+nav#TOC > ul {
+    padding-left: 0;
+}
+
+nav#TOC li {
+    margin-bottom: 0.4em;
+    line-height: 1.5;
+}
+
+nav#TOC li::before {
+    display: none;
+}
+
+nav#TOC a {
+    color: #e2e8f0;
+    border-bottom: none;
+}
+
+nav#TOC a:hover {
+    color: #14b8a6;
 }
 
 /* ==================== Tables ==================== */
@@ -1350,7 +4362,7 @@ kbd {
         padding: 40px 48px;
     }
 
-    pre, blockquote, table, img, h1, h2, h3, h4, h5, h6 {
+    .code-block, blockquote, table, img, h1, h2, h3, h4, h5, h6 {
         page-break-inside: avoid;
     }
 
@@ -1364,7 +4376,7 @@ kbd {
     }
 
     a {
-        color: #60a5fa;
+        color: #14b8a6;
     }
 
     a[href^="http"]::after {
@@ -1390,3 +4402,4 @@ kbd {
     }
 }
 "#;
+
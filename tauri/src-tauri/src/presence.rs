@@ -0,0 +1,153 @@
+use crate::downloader::DownloadManager;
+use discord_rich_presence::activity::{Activity, Timestamps};
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex as AsyncMutex;
+
+/// vget's Discord application id, registered at discord.com/developers —
+/// needed so Discord knows which app's name/icon to attach to the activity.
+const DISCORD_APPLICATION_ID: &str = "1176543210987654321";
+
+/// Discord rate-limits presence updates; polling any faster just gets
+/// silently dropped, so this is also how often the "elapsed" time visibly
+/// ticks for an idle/downloading state.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The Discord IPC client, guarded by a `Mutex` (never a `static mut`) so
+/// connect/update/close always serialize through one place. `None` while
+/// presence is disabled or the client hasn't connected yet.
+static CLIENT: OnceLock<AsyncMutex<Option<DiscordIpcClient>>> = OnceLock::new();
+
+fn client_slot() -> &'static AsyncMutex<Option<DiscordIpcClient>> {
+    CLIENT.get_or_init(|| AsyncMutex::new(None))
+}
+
+/// The one ffmpeg job presence currently reports, if any; written by
+/// `ffmpeg_set_activity`/`ffmpeg_clear_activity` from a job's progress
+/// callback and read back by the poll loop. Takes priority over the
+/// download count below, since it's the more specific activity.
+#[derive(Debug, Clone)]
+struct FfmpegJobActivity {
+    label: String,
+    target: String,
+    progress: Option<f32>,
+}
+
+static FFMPEG_ACTIVITY: OnceLock<AsyncMutex<Option<FfmpegJobActivity>>> = OnceLock::new();
+
+fn ffmpeg_activity_slot() -> &'static AsyncMutex<Option<FfmpegJobActivity>> {
+    FFMPEG_ACTIVITY.get_or_init(|| AsyncMutex::new(None))
+}
+
+/// The `state` string presence last showed, paired with the unix timestamp
+/// it started being shown at — so Discord's elapsed-time counter counts up
+/// from when an activity began instead of resetting to "0s" on every poll
+/// tick. Reset only when `state` actually changes.
+static ACTIVITY_START: OnceLock<AsyncMutex<Option<(String, i64)>>> = OnceLock::new();
+
+fn activity_start_slot() -> &'static AsyncMutex<Option<(String, i64)>> {
+    ACTIVITY_START.get_or_init(|| AsyncMutex::new(None))
+}
+
+/// Record (or update) the ffmpeg job presence currently shows. `progress` is
+/// a 0-100 percentage when the input's duration is known from the preflight
+/// probe, `None` otherwise (presence then omits the percentage).
+pub async fn ffmpeg_set_activity(label: &str, target: &str, progress: Option<f32>) {
+    *ffmpeg_activity_slot().lock().await = Some(FfmpegJobActivity {
+        label: label.to_string(),
+        target: target.to_string(),
+        progress,
+    });
+}
+
+/// Clear the ffmpeg job presence, e.g. once a job completes, fails, or is
+/// cancelled, so the next poll falls back to the download count or idle.
+pub async fn ffmpeg_clear_activity() {
+    *ffmpeg_activity_slot().lock().await = None;
+}
+
+/// Set the enabled flag without touching any IPC connection. Only meant for
+/// startup, before the poll loop has had a chance to connect anything;
+/// runtime toggles should go through `set_enabled` instead.
+pub fn init_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Enable or disable presence updates from `set_presence_enabled`. Disabling
+/// closes the IPC connection immediately rather than waiting for the next
+/// poll tick, so the user's Discord status clears as soon as they turn it off.
+pub async fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+    if !enabled {
+        if let Some(mut client) = client_slot().lock().await.take() {
+            let _ = client.close();
+        }
+    }
+}
+
+/// Start the background task that keeps Discord presence in sync with
+/// `DownloadManager`/ffmpeg job state. Safe to call once at app startup; the
+/// loop simply skips connecting (and keeps any existing connection closed)
+/// whenever presence is disabled.
+pub fn spawn_presence_loop(download_manager: Arc<DownloadManager>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if ENABLED.load(Ordering::SeqCst) {
+                if let Err(e) = tick(&download_manager).await {
+                    eprintln!("[presence] update failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn tick(download_manager: &Arc<DownloadManager>) -> Result<(), String> {
+    let mut slot = client_slot().lock().await;
+    if slot.is_none() {
+        let mut client = DiscordIpcClient::new(DISCORD_APPLICATION_ID).map_err(|e| e.to_string())?;
+        client.connect().map_err(|e| e.to_string())?;
+        *slot = Some(client);
+    }
+    let client = slot.as_mut().expect("just connected above");
+
+    let ffmpeg_activity = ffmpeg_activity_slot().lock().await.clone();
+    let downloading = download_manager.active_count().await;
+
+    let state = match &ffmpeg_activity {
+        Some(job) => match job.progress {
+            Some(pct) => format!("{} {} ({:.0}%)", job.label, job.target, pct),
+            None => format!("{} {}", job.label, job.target),
+        },
+        None if downloading > 0 => format!(
+            "Downloading {} video{}",
+            downloading,
+            if downloading == 1 { "" } else { "s" }
+        ),
+        None => "Idle".to_string(),
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs() as i64;
+
+    let mut start_slot = activity_start_slot().lock().await;
+    let start = match start_slot.as_ref() {
+        Some((last_state, start)) if last_state == &state => *start,
+        _ => now,
+    };
+    *start_slot = Some((state.clone(), start));
+    drop(start_slot);
+
+    let activity = Activity::new()
+        .state(&state)
+        .details("vget")
+        .timestamps(Timestamps::new().start(start));
+
+    client.set_activity(activity).map_err(|e| e.to_string())
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex as AsyncMutex;
+
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Paths currently whitelisted for preview, keyed by the random token in
+/// their URL (`http://127.0.0.1:{port}/{token}`) so an unguessable URL is
+/// required to fetch anything back out of the server.
+type PreviewEntries = Arc<AsyncMutex<HashMap<String, PathBuf>>>;
+
+/// Lazily-started, localhost-only HTTP server that serves whitelisted files
+/// with `Range` support, so `<video>`/`<audio>` elements can seek a preview
+/// without downloading it whole — a plain Tauri URI handler doesn't parse
+/// `Range` at all, which is what causes the app-freeze/seek issues this is
+/// meant to avoid. One instance is managed as Tauri state; its socket binds
+/// on the first `register` call rather than at app startup.
+pub struct PreviewRegistry {
+    addr: AsyncMutex<Option<SocketAddr>>,
+    entries: PreviewEntries,
+}
+
+impl PreviewRegistry {
+    pub fn new() -> Self {
+        Self {
+            addr: AsyncMutex::new(None),
+            entries: Arc::new(AsyncMutex::new(HashMap::new())),
+        }
+    }
+
+    /// Whitelist `path` for preview and return a URL for it, starting the
+    /// server first if this is the first registration.
+    pub async fn register(&self, path: PathBuf) -> Result<String, String> {
+        if !path.is_file() {
+            return Err(format!("File not found: {}", path.display()));
+        }
+
+        let addr = self.ensure_started().await?;
+        let token = uuid::Uuid::new_v4().to_string();
+        self.entries.lock().await.insert(token.clone(), path);
+        Ok(format!("http://{}/{}", addr, token))
+    }
+
+    /// Revoke a previously registered preview, looking it up by the full URL
+    /// `register` returned.
+    pub async fn unregister(&self, url: &str) {
+        if let Some(token) = url.rsplit('/').next() {
+            self.entries.lock().await.remove(token);
+        }
+    }
+
+    async fn ensure_started(&self) -> Result<SocketAddr, String> {
+        let mut addr = self.addr.lock().await;
+        if let Some(bound) = *addr {
+            return Ok(bound);
+        }
+
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .map_err(|e| format!("Failed to bind preview server: {}", e))?;
+        let bound = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read preview server address: {}", e))?;
+
+        let entries = self.entries.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    continue;
+                };
+                let entries = entries.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_one(stream, entries).await {
+                        eprintln!("[preview] connection error: {}", e);
+                    }
+                });
+            }
+        });
+
+        *addr = Some(bound);
+        Ok(bound)
+    }
+}
+
+/// Handle a single connection: read one HTTP request, resolve its token
+/// against `entries`, and stream back the whole file or — when a `Range`
+/// header is present — just the requested byte window.
+async fn serve_one(mut stream: TcpStream, entries: PreviewEntries) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = find_header_end(&buf) {
+            break pos;
+        }
+        if buf.len() > 16 * 1024 {
+            return write_status(&mut stream, 400, "Bad Request").await;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or("");
+    let mut request_parts = request_line.split_whitespace();
+    let method = request_parts.next().unwrap_or("").to_string();
+    let target = request_parts.next().unwrap_or("").to_string();
+    let token = target.trim_start_matches('/').to_string();
+
+    let range_header = lines
+        .find(|l| l.to_ascii_lowercase().starts_with("range:"))
+        .and_then(|l| l.split_once(':'))
+        .map(|(_, v)| v.trim().to_string());
+
+    if method != "GET" && method != "HEAD" {
+        return write_status(&mut stream, 405, "Method Not Allowed").await;
+    }
+
+    let Some(path) = entries.lock().await.get(&token).cloned() else {
+        return write_status(&mut stream, 404, "Not Found").await;
+    };
+
+    let mut file = match tokio::fs::File::open(&path).await {
+        Ok(f) => f,
+        Err(_) => return write_status(&mut stream, 404, "Not Found").await,
+    };
+    let file_size = file.metadata().await?.len();
+    let content_type = guess_content_type(&path);
+
+    let range = range_header.as_deref().and_then(|h| parse_range(h, file_size));
+    let (status, start, end) = match range {
+        Some((s, e)) => ("206 Partial Content", s, e),
+        None => ("200 OK", 0, file_size.saturating_sub(1)),
+    };
+    let length = end.saturating_sub(start) + 1;
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nAccept-Ranges: bytes\r\nContent-Type: {}\r\nContent-Length: {}\r\n",
+        status, content_type, length
+    );
+    if range.is_some() {
+        response.push_str(&format!("Content-Range: bytes {}-{}/{}\r\n", start, end, file_size));
+    }
+    response.push_str("Connection: close\r\n\r\n");
+    stream.write_all(response.as_bytes()).await?;
+
+    if method == "HEAD" {
+        return Ok(());
+    }
+
+    file.seek(std::io::SeekFrom::Start(start)).await?;
+    let mut remaining = length;
+    let mut read_buf = [0u8; READ_CHUNK_SIZE];
+    while remaining > 0 {
+        let want = remaining.min(READ_CHUNK_SIZE as u64) as usize;
+        let n = file.read(&mut read_buf[..want]).await?;
+        if n == 0 {
+            break;
+        }
+        stream.write_all(&read_buf[..n]).await?;
+        remaining -= n as u64;
+    }
+
+    Ok(())
+}
+
+fn find_header_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n").map(|pos| pos + 4)
+}
+
+async fn write_status(stream: &mut TcpStream, code: u16, reason: &str) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        code, reason
+    );
+    stream.write_all(response.as_bytes()).await
+}
+
+/// Parse a `Range: bytes=start-end` header (`end`, or both `start` and
+/// `end`'s prefix, may be omitted per RFC 7233) into an inclusive byte range
+/// clamped to `file_size`. Returns `None` for anything this server doesn't
+/// understand, in which case the caller falls back to a full `200` response.
+fn parse_range(header: &str, file_size: u64) -> Option<(u64, u64)> {
+    let spec = header.strip_prefix("bytes=")?;
+    let (start_str, end_str) = spec.split_once('-')?;
+
+    if start_str.is_empty() {
+        // "bytes=-N" means the last N bytes of the file.
+        let suffix_len: u64 = end_str.parse().ok()?;
+        let start = file_size.saturating_sub(suffix_len);
+        return Some((start, file_size.saturating_sub(1)));
+    }
+
+    let start: u64 = start_str.parse().ok()?;
+    let end = if end_str.is_empty() {
+        file_size.saturating_sub(1)
+    } else {
+        end_str.parse::<u64>().ok()?.min(file_size.saturating_sub(1))
+    };
+
+    if file_size == 0 || start > end || start >= file_size {
+        return None;
+    }
+    Some((start, end))
+}
+
+fn guess_content_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "mp4" | "m4v" => "video/mp4",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "mov" => "video/quicktime",
+        "mp3" => "audio/mpeg",
+        "m4a" | "aac" => "audio/mp4",
+        "wav" => "audio/wav",
+        "opus" | "ogg" => "audio/ogg",
+        "flac" => "audio/flac",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,186 @@
+use crate::config::{get_config, WebDAVServer};
+use futures::stream;
+use reqwest::{Client, StatusCode};
+use std::path::Path;
+use std::time::Instant;
+use tauri::{Emitter, Window};
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+/// Upload a completed download to a configured WebDAV server: creates
+/// `remote_subdir`'s intermediate collections with `MKCOL` as needed, then
+/// `PUT`s the file under HTTP Basic auth from the config entry, streaming it
+/// off disk in chunks so large videos never buffer fully in memory. Progress
+/// is reported via `webdav-upload-progress`/`webdav-upload-complete` events,
+/// mirroring how `SimpleDownloader` reports download progress.
+pub async fn upload_to_webdav(
+    job_id: &str,
+    server_name: &str,
+    local_path: &str,
+    remote_subdir: &str,
+    window: &Window,
+) -> Result<(), String> {
+    let config = get_config().map_err(|e| e.to_string())?;
+    let server = config
+        .webdav_servers
+        .get(server_name)
+        .ok_or_else(|| format!("No WebDAV server configured named '{}'", server_name))?;
+
+    let file_name = Path::new(local_path)
+        .file_name()
+        .ok_or_else(|| format!("Invalid local path: {}", local_path))?
+        .to_string_lossy()
+        .to_string();
+
+    let client = Client::new();
+    ensure_collections(&client, server, remote_subdir).await?;
+
+    let remote_path = join_remote_path(remote_subdir, &file_name);
+    let url = format!("{}/{}", server.url.trim_end_matches('/'), remote_path);
+
+    let total = tokio::fs::metadata(local_path)
+        .await
+        .map_err(|e| format!("Failed to stat {}: {}", local_path, e))?
+        .len();
+
+    let file = File::open(local_path)
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", local_path, e))?;
+
+    let body = reqwest::Body::wrap_stream(upload_progress_stream(
+        file,
+        total,
+        job_id.to_string(),
+        server_name.to_string(),
+        window.clone(),
+    ));
+
+    let response = client
+        .put(&url)
+        .basic_auth(&server.username, Some(&server.password))
+        .header("Content-Length", total.to_string())
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("WebDAV upload request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "WebDAV server rejected upload with status {}",
+            response.status()
+        ));
+    }
+
+    let _ = window.emit(
+        "webdav-upload-complete",
+        serde_json::json!({
+            "jobId": job_id,
+            "serverName": server_name,
+            "remotePath": remote_path,
+            "size": total,
+        }),
+    );
+
+    Ok(())
+}
+
+/// Read `file` in fixed-size chunks as a body stream, emitting
+/// `webdav-upload-progress` at most every 100ms along the way.
+fn upload_progress_stream(
+    file: File,
+    total: u64,
+    job_id: String,
+    server_name: String,
+    window: Window,
+) -> impl futures::Stream<Item = Result<Vec<u8>, std::io::Error>> {
+    stream::unfold(
+        (file, 0u64, Instant::now(), 0u64),
+        move |(mut file, uploaded, mut last_emit, mut last_uploaded)| {
+            let job_id = job_id.clone();
+            let server_name = server_name.clone();
+            let window = window.clone();
+            async move {
+                let mut buf = vec![0u8; 64 * 1024];
+                match file.read(&mut buf).await {
+                    Ok(0) => None,
+                    Ok(n) => {
+                        buf.truncate(n);
+                        let uploaded = uploaded + n as u64;
+
+                        if last_emit.elapsed().as_millis() >= 100 {
+                            let elapsed = last_emit.elapsed().as_secs_f64();
+                            let speed = if elapsed > 0.0 {
+                                ((uploaded - last_uploaded) as f64 / elapsed) as u64
+                            } else {
+                                0
+                            };
+
+                            let _ = window.emit(
+                                "webdav-upload-progress",
+                                serde_json::json!({
+                                    "jobId": job_id,
+                                    "serverName": server_name,
+                                    "uploaded": uploaded,
+                                    "total": total,
+                                    "speed": speed,
+                                }),
+                            );
+
+                            last_emit = Instant::now();
+                            last_uploaded = uploaded;
+                        }
+
+                        Some((Ok(buf), (file, uploaded, last_emit, last_uploaded)))
+                    }
+                    Err(e) => Some((Err(e), (file, uploaded, last_emit, last_uploaded))),
+                }
+            }
+        },
+    )
+}
+
+/// Create every intermediate collection in `remote_subdir` with `MKCOL`,
+/// deepest segment last. A `405 Method Not Allowed` means the collection
+/// already exists, which is not an error here.
+async fn ensure_collections(
+    client: &Client,
+    server: &WebDAVServer,
+    remote_subdir: &str,
+) -> Result<(), String> {
+    let base = server.url.trim_end_matches('/');
+    let mut path = String::new();
+
+    for segment in remote_subdir.split('/').filter(|s| !s.is_empty()) {
+        path.push('/');
+        path.push_str(segment);
+        let url = format!("{}{}", base, path);
+
+        let response = client
+            .request(reqwest::Method::from_bytes(b"MKCOL").unwrap(), &url)
+            .basic_auth(&server.username, Some(&server.password))
+            .send()
+            .await
+            .map_err(|e| format!("MKCOL request failed for {}: {}", url, e))?;
+
+        match response.status() {
+            StatusCode::CREATED | StatusCode::METHOD_NOT_ALLOWED | StatusCode::OK => {}
+            status => {
+                return Err(format!(
+                    "WebDAV server rejected MKCOL for {} with status {}",
+                    url, status
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn join_remote_path(remote_subdir: &str, file_name: &str) -> String {
+    let subdir = remote_subdir.trim_matches('/');
+    if subdir.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{}/{}", subdir, file_name)
+    }
+}